@@ -0,0 +1,136 @@
+use shady_minions::ui::{Button, Card, CardContent, CardHeader, CardTitle, Form, Modal};
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+/// Opens a modal to edit the comment and glyphs of the move under the cursor.
+/// At the start of the game the comment is written before the first move.
+#[function_component(AnnotationEditor)]
+pub fn annotation_editor() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let is_open = use_state(|| false);
+    let cursor = game_ctx.cursor();
+    let node = game_ctx.game_tree().get(cursor).cloned();
+    let is_start = cursor == crate::models::GameTree::ROOT;
+
+    let toggle_open = {
+        let is_open = is_open.clone();
+        Callback::from(move |_: MouseEvent| {
+            is_open.set(!*is_open);
+        })
+    };
+    let onsubmit = {
+        let game_ctx = game_ctx.clone();
+        let is_open = is_open.clone();
+        Callback::from(move |form: web_sys::HtmlFormElement| {
+            let comment = form
+                .get_with_name("comment")
+                .and_then(|n| n.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+                .map(|textarea| textarea.value())
+                .unwrap_or_default();
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::SetComment {
+                id: cursor,
+                comment,
+            });
+            is_open.set(false);
+        })
+    };
+    let nag_buttons = crate::models::NAGS
+        .iter()
+        .map(|(nag, glyph)| {
+            let nag = *nag;
+            let is_set = node.as_ref().is_some_and(|node| node.nags.contains(&nag));
+            let onclick = {
+                let game_ctx = game_ctx.clone();
+                Callback::from(move |_: MouseEvent| {
+                    game_ctx.dispatch(crate::live_game::AnnotatedGameAction::ToggleNag {
+                        id: cursor,
+                        nag,
+                    });
+                })
+            };
+            html! {
+                <Button
+                    {onclick}
+                    r#type={shady_minions::ui::ButtonType::Button}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={if is_set {
+                        shady_minions::ui::ButtonVariant::Normal
+                    } else {
+                        shady_minions::ui::ButtonVariant::Outline
+                    }}>
+                    { *glyph }
+                </Button>
+            }
+        })
+        .collect::<Html>();
+    let clear_nags = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::ClearNags(cursor));
+        })
+    };
+    let title = node
+        .as_ref()
+        .and_then(|node| node.san.as_ref())
+        .map_or_else(
+            || language_ctx.t("annotation_pre_game_comment"),
+            |san| {
+                let prefix = game_ctx
+                    .game_tree()
+                    .move_number_prefix(cursor, true)
+                    .unwrap_or_default();
+                format!("{} {prefix} {san}", language_ctx.t("annotation_title"))
+            },
+        );
+
+    html! {
+        <>
+            <Button
+                onclick={toggle_open}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={shady_minions::ui::ButtonVariant::Outline}>
+                <lucide_yew::MessageSquare class="size-4" />
+            </Button>
+            <Modal {is_open}>
+                <Card class="w-full max-w-sm">
+                    <CardHeader>
+                        <CardTitle>{ title }</CardTitle>
+                    </CardHeader>
+                    <CardContent class="flex flex-col gap-4">
+                        if !is_start {
+                            <div class="space-y-2">
+                                <label class="text-sm font-medium text-foreground">{ language_ctx.t("annotation_glyphs") }</label>
+                                <div class="flex flex-wrap gap-1">
+                                    { nag_buttons }
+                                    <Button
+                                        onclick={clear_nags}
+                                        r#type={shady_minions::ui::ButtonType::Button}
+                                        size={shady_minions::ui::ButtonSize::Small}
+                                        variant={shady_minions::ui::ButtonVariant::Outline}>
+                                        <lucide_yew::Eraser class="size-4" />
+                                    </Button>
+                                </div>
+                            </div>
+                        }
+                        <Form {onsubmit} class="space-y-2">
+                            <label class="text-sm font-medium text-foreground">{ language_ctx.t("annotation_comment") }</label>
+                            // Keyed by the cursor so the text area is refilled when another move is selected
+                            <textarea
+                                key={cursor}
+                                name="comment"
+                                rows="4"
+                                class="w-full rounded-md border border-input bg-background px-3 py-2 text-sm"
+                                placeholder={ language_ctx.t("annotation_comment_placeholder") }
+                                value={node.and_then(|node| node.comment).unwrap_or_default()}
+                            />
+                            <Button r#type={shady_minions::ui::ButtonType::Submit} class="w-full">
+                                { language_ctx.t("annotation_save") }
+                            </Button>
+                        </Form>
+                    </CardContent>
+                </Card>
+            </Modal>
+        </>
+    }
+}
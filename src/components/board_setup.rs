@@ -0,0 +1,391 @@
+use shady_minions::ui::{Button, Card, CardContent, CardHeader, CardTitle, Input, Modal};
+use shakmaty::Position;
+use yew::prelude::*;
+
+const PALETTE_ROLES: [shakmaty::Role; 6] = [
+    shakmaty::Role::King,
+    shakmaty::Role::Queen,
+    shakmaty::Role::Rook,
+    shakmaty::Role::Bishop,
+    shakmaty::Role::Knight,
+    shakmaty::Role::Pawn,
+];
+
+/// Rook squares of each castling right, in FEN order.
+const CASTLING_SQUARES: [(shakmaty::Square, &str); 4] = [
+    (shakmaty::Square::H1, "K"),
+    (shakmaty::Square::A1, "Q"),
+    (shakmaty::Square::H8, "k"),
+    (shakmaty::Square::A8, "q"),
+];
+
+const fn piece_figure(piece: shakmaty::Piece) -> char {
+    match (piece.color, piece.role) {
+        (shakmaty::Color::White, shakmaty::Role::King) => '♔',
+        (shakmaty::Color::White, shakmaty::Role::Queen) => '♕',
+        (shakmaty::Color::White, shakmaty::Role::Rook) => '♖',
+        (shakmaty::Color::White, shakmaty::Role::Bishop) => '♗',
+        (shakmaty::Color::White, shakmaty::Role::Knight) => '♘',
+        (shakmaty::Color::White, shakmaty::Role::Pawn) => '♙',
+        (shakmaty::Color::Black, shakmaty::Role::King) => '♚',
+        (shakmaty::Color::Black, shakmaty::Role::Queen) => '♛',
+        (shakmaty::Color::Black, shakmaty::Role::Rook) => '♜',
+        (shakmaty::Color::Black, shakmaty::Role::Bishop) => '♝',
+        (shakmaty::Color::Black, shakmaty::Role::Knight) => '♞',
+        (shakmaty::Color::Black, shakmaty::Role::Pawn) => '♟',
+    }
+}
+
+/// Starts a new game from a position set up piece by piece or pasted as FEN.
+#[function_component(BoardSetup)]
+pub fn board_setup() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let is_open = use_state(|| false);
+    let setup = use_state(shakmaty::Setup::default);
+    // `None` erases pieces
+    let selected_piece = use_state(|| Some(shakmaty::Role::King.of(shakmaty::Color::White)));
+    let error = use_state(|| None::<String>);
+    let fen = shakmaty::fen::Fen::from_setup((*setup).clone()).to_string();
+
+    let toggle_open = {
+        let is_open = is_open.clone();
+        Callback::from(move |_: MouseEvent| {
+            is_open.set(!*is_open);
+        })
+    };
+    let update_setup = {
+        let setup = setup.clone();
+        let error = error.clone();
+        move |change: Box<dyn Fn(&mut shakmaty::Setup)>| {
+            let setup = setup.clone();
+            let error = error.clone();
+            Callback::from(move |_: MouseEvent| {
+                let mut new_setup = (*setup).clone();
+                change(&mut new_setup);
+                setup.set(new_setup);
+                error.set(None);
+            })
+        }
+    };
+    let on_square = {
+        let setup = setup.clone();
+        let selected_piece = selected_piece.clone();
+        let error = error.clone();
+        Callback::from(move |square: shakmaty::Square| {
+            let mut new_setup = (*setup).clone();
+            match *selected_piece {
+                // Placing the same piece again takes it off
+                Some(piece) if new_setup.board.piece_at(square) != Some(piece) => {
+                    new_setup.board.set_piece_at(square, piece);
+                }
+                _ => {
+                    new_setup.board.remove_piece_at(square);
+                }
+            }
+            setup.set(new_setup);
+            error.set(None);
+        })
+    };
+    let onsubmit = {
+        let setup = setup.clone();
+        let error = error.clone();
+        let invalid_fen = language_ctx.t("board_setup_invalid_fen");
+        Callback::from(move |form: web_sys::HtmlFormElement| {
+            let fen = form
+                .get_with_name("fen")
+                .and_then(|n| {
+                    web_sys::wasm_bindgen::JsCast::dyn_into::<web_sys::HtmlInputElement>(n).ok()
+                })
+                .map(|input| input.value())
+                .unwrap_or_default();
+            match fen.trim().parse::<shakmaty::fen::Fen>() {
+                Ok(fen) => {
+                    setup.set(fen.into_setup());
+                    error.set(None);
+                }
+                Err(_) => error.set(Some(invalid_fen.clone())),
+            }
+        })
+    };
+    let start = {
+        let game_ctx = game_ctx.clone();
+        let is_open = is_open.clone();
+        let error = error.clone();
+        let fen = fen.clone();
+        let message = language_ctx.t("games_replace_current");
+        let game_over = language_ctx.t("board_setup_game_over");
+        let invalid_fen = language_ctx.t("board_setup_invalid_fen");
+        let illegal_position = language_ctx.t("board_setup_illegal_position");
+        Callback::from(move |_: MouseEvent| {
+            match crate::models::position_from_fen(&fen, game_ctx.variant()) {
+                // Imported games may end in such a position, but there's nothing to play from it
+                Ok(position) if position.is_game_over() => error.set(Some(game_over.clone())),
+                Ok(position) => {
+                    let confirmed = game_ctx.game_tree().mainline().is_empty()
+                        || web_sys::window()
+                            .and_then(|window| window.confirm_with_message(&message).ok())
+                            .unwrap_or_default();
+                    if confirmed {
+                        game_ctx.dispatch(crate::live_game::AnnotatedGameAction::SetStartPosition(
+                            position,
+                        ));
+                        is_open.set(false);
+                    }
+                }
+                Err(crate::models::FenError::Invalid) => error.set(Some(invalid_fen.clone())),
+                Err(crate::models::FenError::IllegalPosition) => {
+                    error.set(Some(illegal_position.clone()));
+                }
+            }
+        })
+    };
+
+    let palette = [shakmaty::Color::White, shakmaty::Color::Black]
+        .into_iter()
+        .map(|color| {
+            let buttons = PALETTE_ROLES
+                .iter()
+                .map(|role| {
+                    let piece = role.of(color);
+                    let is_selected = *selected_piece == Some(piece);
+                    let onclick = {
+                        let selected_piece = selected_piece.clone();
+                        Callback::from(move |_: MouseEvent| selected_piece.set(Some(piece)))
+                    };
+                    html! {
+                        <Button
+                            {onclick}
+                            size={shady_minions::ui::ButtonSize::Small}
+                            variant={if is_selected {
+                                shady_minions::ui::ButtonVariant::Normal
+                            } else {
+                                shady_minions::ui::ButtonVariant::Outline
+                            }}>
+                            <span class="text-xl">{ piece_figure(piece) }</span>
+                        </Button>
+                    }
+                })
+                .collect::<Html>();
+            html! { <div class="flex gap-1 justify-center">{ buttons }</div> }
+        })
+        .collect::<Html>();
+    let eraser = {
+        let selected_piece = selected_piece.clone();
+        Callback::from(move |_: MouseEvent| selected_piece.set(None))
+    };
+    let turn = setup.turn;
+    let variant = game_ctx.variant();
+    let turn_button = |color: shakmaty::Color, label: &str| {
+        html! {
+            <Button
+                onclick={update_setup(Box::new(move |setup: &mut shakmaty::Setup| {
+                    setup.turn = color;
+                    // The en passant square depends on the side to move
+                    setup.ep_square = None;
+                }))}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={if turn == color {
+                    shady_minions::ui::ButtonVariant::Normal
+                } else {
+                    shady_minions::ui::ButtonVariant::Outline
+                }}>
+                { label.to_string() }
+            </Button>
+        }
+    };
+    let castling = CASTLING_SQUARES
+        .iter()
+        .map(|(square, label)| {
+            let square = *square;
+            let is_set = setup.castling_rights.contains(square);
+            html! {
+                <Button
+                    onclick={update_setup(Box::new(move |setup: &mut shakmaty::Setup| {
+                        setup.castling_rights.toggle(square);
+                    }))}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={if is_set {
+                        shady_minions::ui::ButtonVariant::Normal
+                    } else {
+                        shady_minions::ui::ButtonVariant::Outline
+                    }}>
+                    { *label }
+                </Button>
+            }
+        })
+        .collect::<Html>();
+    let ep_rank = match turn {
+        shakmaty::Color::White => shakmaty::Rank::Sixth,
+        shakmaty::Color::Black => shakmaty::Rank::Third,
+    };
+    let ep_options = shakmaty::File::ALL
+        .into_iter()
+        .map(|file| {
+            let square = shakmaty::Square::from_coords(file, ep_rank);
+            let is_set = setup.ep_square == Some(square);
+            html! {
+                <Button
+                    onclick={update_setup(Box::new(move |setup: &mut shakmaty::Setup| {
+                        setup.ep_square = (setup.ep_square != Some(square)).then_some(square);
+                    }))}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={if is_set {
+                        shady_minions::ui::ButtonVariant::Normal
+                    } else {
+                        shady_minions::ui::ButtonVariant::Outline
+                    }}>
+                    { square.to_string() }
+                </Button>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <>
+            <Button onclick={toggle_open}
+                variant={shady_minions::ui::ButtonVariant::Outline}
+                size={shady_minions::ui::ButtonSize::Icon}>
+                <lucide_yew::LayoutGrid class="size-7" />
+            </Button>
+            <Modal {is_open}>
+                <Card class="w-full max-w-md max-h-[90vh] overflow-y-auto">
+                    <CardHeader>
+                        <CardTitle>{ language_ctx.t("board_setup_title") }</CardTitle>
+                    </CardHeader>
+                    <CardContent class="flex flex-col gap-4">
+                        if *is_open {
+                            <SetupBoard fen={fen.clone()} onclick={on_square} />
+                        }
+                        <div class="flex flex-col gap-1">
+                            { palette }
+                            <div class="flex gap-1 justify-center">
+                                <Button
+                                    onclick={eraser}
+                                    size={shady_minions::ui::ButtonSize::Small}
+                                    variant={if selected_piece.is_none() {
+                                        shady_minions::ui::ButtonVariant::Normal
+                                    } else {
+                                        shady_minions::ui::ButtonVariant::Outline
+                                    }}>
+                                    <lucide_yew::Eraser class="size-4" />
+                                </Button>
+                                <Button
+                                    onclick={update_setup(Box::new(move |setup: &mut shakmaty::Setup| {
+                                        *setup = variant
+                                            .start_position()
+                                            .into_setup(shakmaty::EnPassantMode::Legal);
+                                    }))}
+                                    size={shady_minions::ui::ButtonSize::Small}
+                                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                                    { language_ctx.t("board_setup_standard") }
+                                </Button>
+                                <Button
+                                    onclick={update_setup(Box::new(|setup: &mut shakmaty::Setup| {
+                                        *setup = shakmaty::Setup::empty();
+                                    }))}
+                                    size={shady_minions::ui::ButtonSize::Small}
+                                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                                    { language_ctx.t("board_setup_clear") }
+                                </Button>
+                            </div>
+                        </div>
+                        <div class="space-y-2">
+                            <label class="text-sm font-medium text-foreground">{ language_ctx.t("board_setup_turn") }</label>
+                            <div class="flex gap-1">
+                                { turn_button(shakmaty::Color::White, &language_ctx.t("common_white")) }
+                                { turn_button(shakmaty::Color::Black, &language_ctx.t("common_black")) }
+                            </div>
+                        </div>
+                        <div class="space-y-2">
+                            <label class="text-sm font-medium text-foreground">{ language_ctx.t("board_setup_castling") }</label>
+                            <div class="flex gap-1">{ castling }</div>
+                        </div>
+                        <div class="space-y-2">
+                            <label class="text-sm font-medium text-foreground">{ language_ctx.t("board_setup_en_passant") }</label>
+                            <div class="flex flex-wrap gap-1">{ ep_options }</div>
+                        </div>
+                        <shady_minions::ui::Form {onsubmit} class="flex gap-2">
+                            <Input
+                                name="fen"
+                                r#type={shady_minions::ui::InputType::Text}
+                                value={fen.clone()}
+                                class="w-full font-mono text-xs" />
+                            <Button r#type={shady_minions::ui::ButtonType::Submit}>
+                                <lucide_yew::ClipboardPaste class="size-4" />
+                            </Button>
+                        </shady_minions::ui::Form>
+                        if let Some(error) = (*error).clone() {
+                            <p class="text-sm text-destructive">{ error }</p>
+                        }
+                        <Button onclick={start}>
+                            { language_ctx.t("board_setup_start") }
+                        </Button>
+                    </CardContent>
+                </Card>
+            </Modal>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct SetupBoardProps {
+    fen: String,
+    onclick: Callback<shakmaty::Square>,
+}
+
+/// Board of the setup being edited, every square can be clicked.
+#[function_component(SetupBoard)]
+fn setup_board(props: &SetupBoardProps) -> Html {
+    let configs = crate::configs::use_annotator_config();
+    let orientation = match configs.playing_as {
+        crate::configs::BoardPlayingSide::White => chessboard_js::ChessboardOrientation::White,
+        crate::configs::BoardPlayingSide::Black => chessboard_js::ChessboardOrientation::Black,
+    };
+    let board_ref = use_node_ref();
+    let board_id = "setup-board";
+    let game_board = use_mut_ref(|| None::<chessboard_js::ChessBoardJs>);
+
+    // The board is drawn again on every change, so the click handlers are set up again too
+    {
+        let board_setting = game_board.clone();
+        let onclick = props.onclick.clone();
+        use_effect_with(
+            (board_ref.clone(), props.fen.clone()),
+            move |(board_ref, fen)| {
+                let board = super::read_only_board(board_id, fen, orientation);
+                *board_setting.borrow_mut() = Some(board);
+
+                if let Some(root_ele) = board_ref.cast::<web_sys::HtmlElement>() {
+                    for square in shakmaty::Square::ALL {
+                        let class_name = format!("square-{}", square);
+                        let elements = root_ele.get_elements_by_class_name(&class_name);
+                        for i in 0..elements.length() {
+                            let Some(html_ele) = elements.item(i).and_then(|el| {
+                                web_sys::wasm_bindgen::JsCast::dyn_into::<web_sys::HtmlElement>(el)
+                                    .ok()
+                            }) else {
+                                continue;
+                            };
+                            let onclick = onclick.clone();
+                            let closure = web_sys::wasm_bindgen::closure::Closure::wrap(Box::new(
+                                move |_event: web_sys::MouseEvent| {
+                                    onclick.emit(square);
+                                },
+                            )
+                                as Box<dyn FnMut(_)>)
+                            .into_js_value();
+                            html_ele.set_onclick(Some(
+                                web_sys::wasm_bindgen::JsCast::unchecked_ref(&closure),
+                            ));
+                        }
+                    }
+                }
+                || {}
+            },
+        );
+    }
+    html! {
+        <div ref={board_ref} id={board_id} class="w-full max-w-xs aspect-square mx-auto" />
+    }
+}
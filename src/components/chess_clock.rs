@@ -0,0 +1,163 @@
+use crate::models::{format_clock, now_millis, ChessClock, TimeControl};
+use shady_minions::ui::{Button, Card, CardContent, CardHeader, CardTitle, Input, Modal};
+use web_sys::wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+/// Sets the time control of an over-the-board game, or stops timing it.
+#[function_component(TimeControlSetup)]
+pub fn time_control_setup() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let is_open = use_state(|| false);
+    let current = game_ctx
+        .clock()
+        .map_or(TimeControl::new(600, 0, 0), ChessClock::time_control);
+
+    let toggle_open = {
+        let is_open = is_open.clone();
+        Callback::from(move |_: MouseEvent| {
+            is_open.set(!*is_open);
+        })
+    };
+    let onsubmit = {
+        let game_ctx = game_ctx.clone();
+        let is_open = is_open.clone();
+        Callback::from(move |form: web_sys::HtmlFormElement| {
+            let field = |name: &str| {
+                form.get_with_name(name)
+                    .map(JsCast::unchecked_into::<web_sys::HtmlInputElement>)
+                    .and_then(|input| input.value().trim().parse::<u32>().ok())
+                    .unwrap_or_default()
+            };
+            let minutes = field("base_minutes");
+            if minutes == 0 {
+                return;
+            }
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::SetTimeControl(Some(
+                TimeControl::new(minutes * 60, field("increment"), field("delay")),
+            )));
+            is_open.set(false);
+        })
+    };
+    let remove = {
+        let game_ctx = game_ctx.clone();
+        let is_open = is_open.clone();
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::SetTimeControl(None));
+            is_open.set(false);
+        })
+    };
+    let number_field = |name: &'static str, label: String, value: u32| {
+        html! {
+            <div class="space-y-2">
+                <label class="text-sm font-medium text-foreground">{ label }</label>
+                <Input
+                    {name}
+                    r#type={shady_minions::ui::InputType::Number}
+                    value={value.to_string()}
+                    class="w-full" />
+            </div>
+        }
+    };
+
+    html! {
+        <>
+            <Button onclick={toggle_open}
+                variant={shady_minions::ui::ButtonVariant::Outline}
+                size={shady_minions::ui::ButtonSize::Icon}>
+                <lucide_yew::Timer class="size-7" />
+            </Button>
+            <Modal {is_open}>
+                <Card class="w-full max-w-sm">
+                    <CardHeader>
+                        <CardTitle>{ language_ctx.t("clock_title") }</CardTitle>
+                    </CardHeader>
+                    <CardContent>
+                        <shady_minions::ui::Form {onsubmit} class="space-y-4">
+                            { number_field("base_minutes", language_ctx.t("clock_base_minutes"), current.base / 60) }
+                            { number_field("increment", language_ctx.t("clock_increment"), current.increment) }
+                            { number_field("delay", language_ctx.t("clock_delay"), current.delay) }
+                            <Button r#type={shady_minions::ui::ButtonType::Submit} class="w-full">
+                                { language_ctx.t("common_save") }
+                            </Button>
+                        </shady_minions::ui::Form>
+                        if game_ctx.clock().is_some() {
+                            <Button
+                                onclick={remove}
+                                class="w-full mt-2"
+                                variant={shady_minions::ui::ButtonVariant::Destructive}>
+                                { language_ctx.t("clock_remove") }
+                            </Button>
+                        }
+                    </CardContent>
+                </Card>
+            </Modal>
+        </>
+    }
+}
+
+/// Times left to both sides of a timed game, the running side is highlighted.
+///
+/// Sides switch when a move is played, the middle button starts or pauses the clock.
+#[function_component(ChessClockPanel)]
+pub fn chess_clock_panel() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let force_update = use_force_update();
+    let running_side = game_ctx.clock().and_then(ChessClock::running_side);
+    // Redraws the running time, the clock itself only changes when pressed
+    use_effect_with(running_side.is_some(), move |running| {
+        let interval = running.then(|| {
+            gloo::timers::callback::Interval::new(200, move || force_update.force_update())
+        });
+        move || drop(interval)
+    });
+    let Some(clock) = game_ctx.clock() else {
+        return html! {};
+    };
+    let now = now_millis();
+    let toggle = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(if running_side.is_some() {
+                crate::live_game::AnnotatedGameAction::PauseClock
+            } else {
+                crate::live_game::AnnotatedGameAction::StartClock
+            });
+        })
+    };
+    let side = |color: shakmaty::Color| {
+        let remaining = clock.remaining(color, now);
+        let class = classes!(
+            "flex-1",
+            "text-center",
+            "font-mono",
+            "text-lg",
+            "rounded-md",
+            "py-1",
+            if running_side == Some(color) {
+                "bg-secondary text-white font-semibold"
+            } else {
+                "bg-background text-muted"
+            },
+            (remaining == 0).then_some("text-destructive"),
+        );
+        html! { <span {class}>{ format_clock(remaining) }</span> }
+    };
+
+    html! {
+        <div class="flex gap-2 items-center w-full mt-2">
+            { side(shakmaty::Color::White) }
+            <Button
+                onclick={toggle}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={shady_minions::ui::ButtonVariant::Outline}>
+                if running_side.is_some() {
+                    <lucide_yew::Pause class="size-4" />
+                } else {
+                    <lucide_yew::Play class="size-4" />
+                }
+            </Button>
+            { side(shakmaty::Color::Black) }
+        </div>
+    }
+}
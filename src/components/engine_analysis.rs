@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::models::{
+    nag_group, parse_info_line, pv_sans, EngineLine, GameTree, GameVariant, MoveId, MoveJudgement,
+    NagGroup,
+};
+use shady_minions::ui::{Button, Card, CardContent, CardHeader, CardTitle};
+use shakmaty::Position;
+use web_sys::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use yew::prelude::*;
+
+/// Stockfish build running as a worker script, served with the app so
+/// analysis works offline.
+const ENGINE_SCRIPT: &str = "/public/engine/stockfish.js";
+/// Depth every position is searched to, enough to catch blunders quickly on a phone.
+const ANALYSIS_DEPTH: u32 = 14;
+/// Moves of the best line shown.
+const BEST_LINE_LENGTH: usize = 6;
+
+/// UCI engine running in a Web Worker, terminated when dropped.
+struct EngineWorker {
+    worker: web_sys::Worker,
+    _onmessage: Closure<dyn FnMut(web_sys::MessageEvent)>,
+    _onerror: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl EngineWorker {
+    /// Starts the engine, every line it prints goes to `on_line`.
+    fn spawn(on_line: Callback<String>, on_error: Callback<()>) -> Result<Self, JsValue> {
+        let worker = web_sys::Worker::new(ENGINE_SCRIPT)?;
+        let onmessage = Closure::<dyn FnMut(_)>::new(move |event: web_sys::MessageEvent| {
+            if let Some(text) = event.data().as_string() {
+                for line in text.lines() {
+                    on_line.emit(line.to_string());
+                }
+            }
+        });
+        let onerror = Closure::<dyn FnMut(_)>::new(move |_: web_sys::Event| on_error.emit(()));
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        worker.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        Ok(Self {
+            worker,
+            _onmessage: onmessage,
+            _onerror: onerror,
+        })
+    }
+    fn send(&self, command: &str) {
+        if let Err(e) = self.worker.post_message(&JsValue::from_str(command)) {
+            web_sys::console::error_1(&e);
+        }
+    }
+}
+
+impl Drop for EngineWorker {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+/// Positions of the main line searched one after the other, with the lines found so far.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct AnalysisState {
+    /// Positions waiting for the engine, with the side to move in each.
+    queue: Vec<(MoveId, shakmaty::Color)>,
+    current: Option<(MoveId, shakmaty::Color)>,
+    lines: HashMap<MoveId, EngineLine>,
+    total: usize,
+    failed: bool,
+}
+
+enum AnalysisAction {
+    Start(Vec<(MoveId, shakmaty::Color)>),
+    EngineOutput(String),
+    Failed,
+}
+
+impl Reducible for AnalysisState {
+    type Action = AnalysisAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            AnalysisAction::Start(mut queue) => {
+                let total = queue.len();
+                let current = (!queue.is_empty()).then(|| queue.remove(0));
+                Rc::new(Self {
+                    queue,
+                    current,
+                    lines: HashMap::new(),
+                    total,
+                    failed: false,
+                })
+            }
+            AnalysisAction::EngineOutput(line) => {
+                let Some((id, turn)) = self.current else {
+                    return self;
+                };
+                let mut state = (*self).clone();
+                if line.starts_with("bestmove") {
+                    state.current = (!state.queue.is_empty()).then(|| state.queue.remove(0));
+                } else if let Some(engine_line) = parse_info_line(&line, turn) {
+                    state.lines.insert(id, engine_line);
+                } else {
+                    return self;
+                }
+                Rc::new(state)
+            }
+            AnalysisAction::Failed => Rc::new(Self {
+                queue: vec![],
+                current: None,
+                failed: true,
+                ..(*self).clone()
+            }),
+        }
+    }
+}
+
+impl AnalysisState {
+    fn is_running(&self) -> bool {
+        self.current.is_some()
+    }
+    fn done(&self) -> usize {
+        self.total - self.queue.len() - usize::from(self.current.is_some())
+    }
+    /// Main line moves the eval drop flags, skipping moves that already
+    /// have a move assessment.
+    fn suggestions(&self, tree: &GameTree) -> Vec<(MoveId, MoveJudgement)> {
+        tree.mainline()
+            .into_iter()
+            .filter(|id| {
+                tree.get(*id).is_some_and(|node| {
+                    !node
+                        .nags
+                        .iter()
+                        .any(|nag| nag_group(*nag) == NagGroup::Move)
+                })
+            })
+            .filter_map(|id| {
+                let before = self.lines.get(&tree.parent(id)?)?.evaluation;
+                let after = self.lines.get(&id)?.evaluation;
+                let mover = tree.move_number(id).1;
+                MoveJudgement::of_move(before, after, mover).map(|judgement| (id, judgement))
+            })
+            .collect()
+    }
+}
+
+/// Engine analysis of the main line, run locally in a worker.
+///
+/// Shows the eval and best line at the move cursor, and offers the
+/// glyphs of the moves the eval drop flags. The evals are charted over the
+/// material balance in [`GameGraph`](crate::components::GameGraph).
+#[function_component(EngineAnalysis)]
+pub fn engine_analysis() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let notation = crate::configs::use_annotator_config().san_notation();
+    let analysis = use_reducer(AnalysisState::default);
+    let engine = use_mut_ref(|| None::<EngineWorker>);
+    let variant = game_ctx.variant();
+
+    // Every new position is sent to the engine once the previous search is over
+    {
+        let engine = engine.clone();
+        let tree = game_ctx.game_tree().clone();
+        use_effect_with(analysis.current, move |current| {
+            if let (Some((id, _)), Some(engine)) = (current, engine.borrow().as_ref()) {
+                let fen = shakmaty::fen::Fen::from_position(
+                    tree.position(*id),
+                    shakmaty::EnPassantMode::Legal,
+                );
+                engine.send(&format!("position fen {fen}"));
+                engine.send(&format!("go depth {ANALYSIS_DEPTH}"));
+            }
+            || {}
+        });
+    }
+
+    let evaluations = analysis
+        .lines
+        .iter()
+        .map(|(id, line)| (*id, line.evaluation))
+        .collect::<HashMap<_, _>>();
+    // Only variants the engine plays are analysed, the others just get the material chart
+    if !matches!(variant, GameVariant::Standard | GameVariant::Chess960) {
+        return html! { <crate::components::GameGraph /> };
+    }
+
+    let start = {
+        let analysis = analysis.clone();
+        let engine = engine.clone();
+        let tree = game_ctx.game_tree().clone();
+        Callback::from(move |_: MouseEvent| {
+            let on_line = {
+                let analysis = analysis.clone();
+                Callback::from(move |line| analysis.dispatch(AnalysisAction::EngineOutput(line)))
+            };
+            let on_error = {
+                let analysis = analysis.clone();
+                Callback::from(move |()| analysis.dispatch(AnalysisAction::Failed))
+            };
+            let worker = match EngineWorker::spawn(on_line, on_error) {
+                Ok(worker) => worker,
+                Err(e) => {
+                    web_sys::console::error_1(&e);
+                    analysis.dispatch(AnalysisAction::Failed);
+                    return;
+                }
+            };
+            worker.send("uci");
+            if variant == GameVariant::Chess960 {
+                worker.send("setoption name UCI_Chess960 value true");
+            }
+            worker.send("ucinewgame");
+            *engine.borrow_mut() = Some(worker);
+            let positions = std::iter::once(GameTree::ROOT)
+                .chain(tree.mainline())
+                .map(|id| (id, tree.position(id).turn()))
+                .collect();
+            analysis.dispatch(AnalysisAction::Start(positions));
+        })
+    };
+
+    let tree = game_ctx.game_tree();
+    let cursor = game_ctx.cursor();
+    let cursor_line = analysis.lines.get(&cursor).map(|line| {
+        let best_line = pv_sans(&tree.position(cursor), &line.pv)
+            .iter()
+            .take(BEST_LINE_LENGTH)
+            .map(|san| notation.display(san))
+            .collect::<Vec<_>>()
+            .join(" ");
+        html! {
+            <div class="flex gap-2 items-baseline">
+                <span class="font-mono font-semibold">{ line.evaluation.display() }</span>
+                <span class="text-xs text-muted-foreground">
+                    { format!("{} {}", language_ctx.t("analysis_best_line"), best_line) }
+                </span>
+            </div>
+        }
+    });
+    let suggestions = analysis.suggestions(tree);
+    let accept = |suggestions: Vec<(MoveId, MoveJudgement)>| {
+        let game_ctx = game_ctx.clone();
+        let nags = suggestions
+            .iter()
+            .map(|(id, judgement)| (*id, judgement.nag()))
+            .collect::<Vec<_>>();
+        // Accepting several suggestions at once is undone at once
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::ToggleNags(
+                nags.clone(),
+            ));
+        })
+    };
+    let judgement_text = |judgement: MoveJudgement| match judgement {
+        MoveJudgement::Inaccuracy => language_ctx.t("analysis_inaccuracy"),
+        MoveJudgement::Mistake => language_ctx.t("analysis_mistake"),
+        MoveJudgement::Blunder => language_ctx.t("analysis_blunder"),
+    };
+
+    html! {
+        <>
+        <crate::components::GameGraph {evaluations} />
+        <Card class="w-full mb-6">
+            <CardHeader>
+                <CardTitle>{ language_ctx.t("analysis_title") }</CardTitle>
+            </CardHeader>
+            <CardContent class="space-y-3">
+                if analysis.failed {
+                    <p class="text-sm text-destructive">{ language_ctx.t("analysis_unavailable") }</p>
+                } else if analysis.total == 0 {
+                    <Button onclick={start} class="w-full">
+                        <lucide_yew::Cpu class="size-5" />
+                        <span class="ml-2">{ language_ctx.t("analysis_start") }</span>
+                    </Button>
+                } else if analysis.is_running() {
+                    <p class="text-sm text-muted-foreground">
+                        { format!("{} {}/{}", language_ctx.t("analysis_running"), analysis.done(), analysis.total) }
+                    </p>
+                }
+                { cursor_line.unwrap_or_default() }
+                if !suggestions.is_empty() {
+                    <div class="space-y-1">
+                        { for suggestions.iter().map(|(id, judgement)| {
+                            let label = tree.move_number_prefix(*id, true).unwrap_or_default();
+                            let san = tree
+                                .get(*id)
+                                .and_then(|node| node.san.as_ref())
+                                .map(|san| notation.display(san))
+                                .unwrap_or_default();
+                            html! {
+                                <div class="flex gap-2 items-center text-sm">
+                                    <span class="flex-1">
+                                        { format!("{label} {san}{} ", crate::models::nag_glyph(judgement.nag())) }
+                                        <span class="text-muted-foreground">{ judgement_text(*judgement) }</span>
+                                    </span>
+                                    <Button
+                                        onclick={accept(vec![(*id, *judgement)])}
+                                        size={shady_minions::ui::ButtonSize::Small}
+                                        variant={shady_minions::ui::ButtonVariant::Outline}>
+                                        { language_ctx.t("analysis_accept") }
+                                    </Button>
+                                </div>
+                            }
+                        }) }
+                        <Button onclick={accept(suggestions.clone())} class="w-full mt-2">
+                            { language_ctx.t("analysis_accept_all") }
+                        </Button>
+                    </div>
+                }
+            </CardContent>
+        </Card>
+        </>
+    }
+}
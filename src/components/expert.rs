@@ -6,46 +6,44 @@ pub fn expert_annotation() -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
     let next_move = use_state(|| None::<String>);
     let legal_moves = use_state(|| game_ctx.legal_moves());
-    let last_position = game_ctx.last_game_position();
+    let last_position = game_ctx.cursor_position();
+    let notation = crate::configs::use_annotator_config().san_notation();
     let ready_move = use_state(|| None::<shakmaty::Move>);
+    let pending_move = use_state(|| None::<shakmaty::Move>);
 
+    // Typing starts over whenever the cursor moves to another position
     {
+        let next_move = next_move.clone();
+        let ready_move = ready_move.clone();
         let legal_moves = legal_moves.clone();
-        let last_position = last_position.clone();
-        use_effect_with(next_move.clone(), move |next| {
-            if let Some(next) = next.as_ref() {
-                let mut new_moves = (*legal_moves).clone();
-                new_moves.retain(|m| {
-                    let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().strip_prefix(next).is_some()
-                });
-                legal_moves.set(new_moves);
-            } else {
-                legal_moves.set(game_ctx.legal_moves());
-            }
+        let game_ctx = game_ctx.clone();
+        use_effect_with(game_ctx.cursor(), move |_| {
+            next_move.set(None);
+            ready_move.set(None);
+            legal_moves.set(game_ctx.legal_moves());
             || {}
         });
     }
 
+    // Narrows from every legal move, so deleting a letter brings back the moves it ruled out
     {
         let legal_moves = legal_moves.clone();
         let ready_move = ready_move.clone();
         use_effect_with(next_move.clone(), move |next| {
+            let mut new_moves = game_ctx.legal_moves();
             if let Some(next) = next.as_ref() {
-                let matching_move = legal_moves
-                    .iter()
-                    .filter_map(|m| {
-                        let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                        san.to_string().strip_prefix(next)?;
-                        Some(m.clone())
-                        //san.to_string().contains(next).then_some(Some(m))
-                    })
-                    .collect::<Vec<_>>();
-                if matching_move.len() == 1 {
-                    let m = matching_move.first().cloned();
-                    ready_move.set(m);
-                }
+                new_moves.retain(|m| {
+                    let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
+                    notation.display(&san).starts_with(next.as_str())
+                });
             }
+            // Only a single match can be played, Enter does nothing while the move is ambiguous
+            let single_match = match new_moves.as_slice() {
+                [m] if next.is_some() => Some(m.clone()),
+                _ => None,
+            };
+            ready_move.set(single_match);
+            legal_moves.set(new_moves);
             || {}
         });
     }
@@ -54,12 +52,14 @@ pub fn expert_annotation() -> Html {
         next_move,
         ready_move: ready_move.clone(),
         legal_moves: legal_moves.clone(),
+        pending_move: pending_move.clone(),
     };
 
     html! {
         <>
             <AnnotationDisplay ..props.clone() />
             <AnnotationCalculator ..props />
+            <crate::components::PlayMovePrompt pending={pending_move} />
         </>
     }
 }
@@ -69,17 +69,20 @@ pub struct ExpertAnnotationProps {
     pub next_move: UseStateHandle<Option<String>>,
     pub legal_moves: UseStateHandle<Vec<shakmaty::Move>>,
     pub ready_move: UseStateHandle<Option<shakmaty::Move>>,
+    pub pending_move: UseStateHandle<Option<shakmaty::Move>>,
 }
 
 #[function_component(AnnotationDisplay)]
 pub fn annotation_display(props: &ExpertAnnotationProps) -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
     let language_ctx = crate::contexts::language::use_language_ctx();
-    let last_position = game_ctx.last_game_position();
+    let last_position = game_ctx.cursor_position();
+    let notation = crate::configs::use_annotator_config().san_notation();
     let ExpertAnnotationProps {
         next_move,
         legal_moves,
         ready_move: _,
+        pending_move: _,
     } = props;
     let is_selecting = next_move.is_some();
 
@@ -90,6 +93,7 @@ pub fn annotation_display(props: &ExpertAnnotationProps) -> Html {
             .enumerate()
             .fold(String::new(), |acc, (i, m)| {
                 let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
+                let san = notation.display(&san);
                 if i == 0 {
                     format!("{acc}: {san}",)
                 } else {
@@ -138,11 +142,13 @@ pub fn annotation_display(props: &ExpertAnnotationProps) -> Html {
 #[function_component(SanMoveBlocks)]
 pub fn san_move_blocks(props: &ExpertAnnotationProps) -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
-    let last_position = game_ctx.last_game_position();
+    let last_position = game_ctx.cursor_position();
+    let notation = crate::configs::use_annotator_config().san_notation();
     let ExpertAnnotationProps {
         next_move: _,
         legal_moves,
         ready_move: _,
+        pending_move: _,
     } = props;
 
     html! {
@@ -162,7 +168,7 @@ pub fn san_move_blocks(props: &ExpertAnnotationProps) -> Html {
                             "justify-center",
                             "flex-col"
                         )}>
-                        <span>{san.to_string()}</span>
+                        <span>{notation.display(&san)}</span>
                     </Button>
                 }
             }).collect::<Html>()}
@@ -173,11 +179,13 @@ pub fn san_move_blocks(props: &ExpertAnnotationProps) -> Html {
 #[function_component(AnnotationCalculator)]
 pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
-    let last_position = game_ctx.last_game_position();
+    let last_position = game_ctx.cursor_position();
+    let notation = crate::configs::use_annotator_config().san_notation();
     let ExpertAnnotationProps {
         next_move,
         legal_moves,
         ready_move,
+        pending_move,
     } = props;
     let onclick = {
         let next_move = next_move.clone();
@@ -186,7 +194,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
         Callback::from(move |input_event: String| {
             if legal_moves.iter().any(|m| {
                 let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                san.to_string().contains(&input_event)
+                notation.display(&san).contains(&input_event)
             }) {
                 let mut new_move = (*next_move).clone().unwrap_or_default();
                 new_move.push_str(&input_event);
@@ -194,12 +202,14 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
             }
         })
     };
+    let has_drops = game_ctx.variant().has_drops();
     let play_move = {
         let ready_move = ready_move.clone();
         let next_move = next_move.clone();
-        Callback::from(move |_| {
+        let pending_move = pending_move.clone();
+        Callback::from(move |()| {
             if let Some(m) = ready_move.as_ref() {
-                game_ctx.dispatch(crate::live_game::AnnotatedGameAction::PlayMove(m.clone()));
+                crate::components::play_or_prompt(&game_ctx, &pending_move, m.clone());
             }
             next_move.set(None);
             ready_move.set(None);
@@ -207,65 +217,76 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
     };
     let clear = {
         let next_move = next_move.clone();
-        Callback::from(move |_| {
+        Callback::from(move |()| {
             next_move.set(None);
         })
     };
+    let calculator_ref = use_node_ref();
+    // Typing SAN on a physical keyboard goes through the same keys as the buttons
+    {
+        let onclick = onclick.clone();
+        let play_move = play_move.clone();
+        let clear = clear.clone();
+        let next_move = next_move.clone();
+        let calculator_ref = calculator_ref.clone();
+        crate::components::use_keydown(
+            (props.clone(), notation),
+            Callback::from(move |event: web_sys::KeyboardEvent| {
+                if event.ctrl_key()
+                    || event.meta_key()
+                    || event.alt_key()
+                    || crate::components::is_typing_in_field(&event)
+                    || !crate::components::is_shown(&calculator_ref)
+                {
+                    return;
+                }
+                match event.key().as_str() {
+                    "Enter" => play_move.emit(()),
+                    "Escape" => clear.emit(()),
+                    "Backspace" => {
+                        let mut typed = (*next_move).clone().unwrap_or_default();
+                        typed.pop();
+                        next_move.set((!typed.is_empty()).then_some(typed));
+                    }
+                    // Castling is often typed with lowercase letters or zeros
+                    "o" | "0" => onclick.emit("O".to_string()),
+                    key if key.chars().count() == 1 => {
+                        // Piece letters are shown as figurines when those are enabled
+                        let typed = key
+                            .chars()
+                            .next()
+                            .and_then(|letter| notation.role_from_letter(letter))
+                            .map_or_else(
+                                || key.to_string(),
+                                |role| notation.symbol(role).to_string(),
+                            );
+                        onclick.emit(typed);
+                    }
+                    _ => return,
+                }
+                event.prevent_default();
+            }),
+        );
+    }
     let input_class = classes!("h-fit", "text-2xl", "font-bold",);
     html! {
-        <div class="">
+        <div ref={calculator_ref} class="">
             <div class="flex space-y-1 space-x-1">
-            <Input
-                class={input_class.clone()}
-                disabled={
-                    !legal_moves.iter()
-                    .any(|m| {
-                    let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("N")})
-                }
-                onclick={onclick.clone()}
-                r#type={InputType::Button}
-                value={"N"} />
-            <Input
-                class={input_class.clone()}
-                disabled={
-                    !legal_moves.iter().any(|m| {
-                    let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("B")})
-                }
-                onclick={onclick.clone()}
-                r#type={InputType::Button}
-                value={"B"} />
-            <Input
-                class={input_class.clone()}
-                disabled={
-                    !legal_moves.iter().any(|m| {
-                    let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("R")})
-                }
-                onclick={onclick.clone()}
-                r#type={InputType::Button}
-                value={"R"} />
-            <Input
-                class={input_class.clone()}
-                disabled={
-                    !legal_moves.iter().any(|m| {
-                    let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("Q")})
-                }
-                onclick={onclick.clone()}
-                r#type={InputType::Button}
-                value={"Q"} />
-            <Input
-                class={input_class.clone()}
-                disabled={
-                    !legal_moves.iter().any(|m| {
-                    let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("K")})
+            { for crate::models::SanNotation::PIECES.into_iter().map(|role| {
+                let symbol = notation.symbol(role).to_string();
+                html! {
+                    <Input
+                        class={input_class.clone()}
+                        disabled={
+                            !legal_moves.iter().any(|m| {
+                            let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
+                            notation.display(&san).contains(&symbol)})
+                        }
+                        onclick={onclick.clone()}
+                        r#type={InputType::Button}
+                        value={symbol.clone()} />
                 }
-                onclick={onclick.clone()}
-                r#type={InputType::Button}
-                value={"K"} />
+            }) }
             </div>
             <div class="flex space-y-1 space-x-1">
             <Input
@@ -275,7 +296,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("a")})
+                    notation.display(&san).contains("a")})
                 }
                 value={"a"} />
             <Input
@@ -283,7 +304,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("b")})
+                    notation.display(&san).contains("b")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -293,7 +314,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("c")})
+                    notation.display(&san).contains("c")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -303,7 +324,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("d")})
+                    notation.display(&san).contains("d")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -313,7 +334,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("e")})
+                    notation.display(&san).contains("e")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -323,7 +344,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("f")})
+                    notation.display(&san).contains("f")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -333,7 +354,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("g")})
+                    notation.display(&san).contains("g")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -343,7 +364,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("h")})
+                    notation.display(&san).contains("h")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -355,7 +376,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("1")})
+                    notation.display(&san).contains("1")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -365,7 +386,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("2")})
+                    notation.display(&san).contains("2")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -375,7 +396,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("3")})
+                    notation.display(&san).contains("3")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -385,7 +406,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("4")})
+                    notation.display(&san).contains("4")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -395,7 +416,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("5")})
+                    notation.display(&san).contains("5")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -405,7 +426,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("6")})
+                    notation.display(&san).contains("6")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -415,7 +436,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("7")})
+                    notation.display(&san).contains("7")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -425,7 +446,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    !san.to_string().contains("8")})
+                    !notation.display(&san).contains("8")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -437,7 +458,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("x")})
+                    notation.display(&san).contains("x")})
                 }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -447,7 +468,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("+")
+                    notation.display(&san).contains("+")
                 }) }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -457,7 +478,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("#")
+                    notation.display(&san).contains("#")
                 }) }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
@@ -467,18 +488,31 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                 disabled={
                     !legal_moves.iter().any(|m| {
                     let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
-                    san.to_string().contains("O")
+                    notation.display(&san).contains("O")
                 }) }
                 onclick={onclick.clone()}
                 r#type={InputType::Button}
                 value={"O"} />
+            // Crazyhouse drops are written as `N@e4`
+            if has_drops {
+                <Input
+                    class={input_class.clone()}
+                    disabled={
+                        !legal_moves.iter().any(|m| {
+                        let san = shakmaty::san::SanPlus::from_move(last_position.clone(), m);
+                        notation.display(&san).contains("@")
+                    }) }
+                    onclick={onclick.clone()}
+                    r#type={InputType::Button}
+                    value={"@"} />
+            }
             <Button
                 class={classes!(
                     "w-full",
                     "h-full",
                 )}
                 variant={shady_minions::ui::ButtonVariant::Destructive}
-                onclick={clear} >
+                onclick={clear.reform(|_: MouseEvent| ())} >
                 <lucide_yew::Delete class="size-8" />
             </Button>
             <Button
@@ -501,7 +535,7 @@ pub fn annotation_calculator(props: &ExpertAnnotationProps) -> Html {
                         "pointer-events-auto"
                     },
                 )}
-                onclick={play_move} >
+                onclick={play_move.reform(|_: MouseEvent| ())} >
                 <lucide_yew::Send class="size-8" />
             </Button>
             </div>
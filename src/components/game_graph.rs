@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use crate::models::{Evaluation, GameTree, MoveId};
+use shakmaty::Position;
+use yew::prelude::*;
+
+/// Horizontal room given to each ply, in SVG units.
+const PLY_WIDTH: i32 = 10;
+/// Half the height of the chart, the middle line is an equal game.
+const HALF_HEIGHT: i32 = 50;
+/// Material difference at the top or bottom of the chart, in pawns.
+const MATERIAL_RANGE: i32 = 10;
+/// Eval at the top or bottom of the chart, in centipawns.
+const EVAL_RANGE: i32 = 1000;
+
+/// Material of one side counted the usual way, pawns 1, minor pieces 3, rooks 5, queens 9.
+fn material_value(material: &shakmaty::ByRole<u8>) -> i32 {
+    i32::from(material.pawn)
+        + 3 * i32::from(material.knight)
+        + 3 * i32::from(material.bishop)
+        + 5 * i32::from(material.rook)
+        + 9 * i32::from(material.queen)
+}
+
+/// Material balance after every ply of the main line, white's material minus black's.
+fn material_balance(tree: &GameTree) -> Vec<(MoveId, i32)> {
+    std::iter::once(GameTree::ROOT)
+        .chain(tree.mainline())
+        .map(|id| {
+            let material = tree.position(id).board().material();
+            (
+                id,
+                material_value(&material.white) - material_value(&material.black),
+            )
+        })
+        .collect()
+}
+
+/// Height of `value` in the chart, out of `range` either way.
+fn chart_y(value: i32, range: i32) -> i32 {
+    -(value.clamp(-range, range) * HALF_HEIGHT / range)
+}
+
+fn polyline_points(points: impl Iterator<Item = (usize, i32)>) -> String {
+    points
+        .map(|(index, y)| format!("{},{y}", index as i32 * PLY_WIDTH))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct GameGraphProps {
+    /// Engine evals of the main line positions, charted when there are any.
+    #[prop_or_default]
+    pub evaluations: HashMap<MoveId, Evaluation>,
+}
+
+/// Chart of the material balance along the main line, with the eval curve
+/// once the engine analysed the game. Clicking the chart jumps to that ply.
+#[function_component(GameGraph)]
+pub fn game_graph(props: &GameGraphProps) -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let tree = game_ctx.game_tree();
+    let balance = material_balance(tree);
+    if balance.len() < 2 {
+        return html! {};
+    }
+    let width = (balance.len() as i32 - 1) * PLY_WIDTH;
+    let material_points = polyline_points(
+        balance
+            .iter()
+            .enumerate()
+            .map(|(index, (_, value))| (index, chart_y(*value, MATERIAL_RANGE))),
+    );
+    let eval_points = polyline_points(balance.iter().enumerate().filter_map(|(index, (id, _))| {
+        let evaluation = props.evaluations.get(id)?;
+        Some((index, chart_y(evaluation.score(), EVAL_RANGE)))
+    }));
+    let cursor_x = balance
+        .iter()
+        .position(|(id, _)| *id == game_ctx.cursor())
+        .map(|index| index as i32 * PLY_WIDTH);
+
+    html! {
+        <svg
+            class="w-full h-24 mb-6 bg-background rounded-md cursor-pointer"
+            viewBox={format!("0 {} {width} {}", -HALF_HEIGHT, 2 * HALF_HEIGHT)}
+            preserveAspectRatio="none">
+            <line x1="0" y1="0" x2={width.to_string()} y2="0"
+                stroke="currentColor" class="text-muted" stroke-width="1"
+                vector-effect="non-scaling-stroke" />
+            <polyline points={material_points} fill="none"
+                stroke="currentColor" class="text-muted-foreground" stroke-width="1.5"
+                vector-effect="non-scaling-stroke" />
+            if !props.evaluations.is_empty() {
+                <polyline points={eval_points} fill="none"
+                    stroke="currentColor" class="text-white" stroke-width="2"
+                    vector-effect="non-scaling-stroke" />
+            }
+            if let Some(x) = cursor_x {
+                <line x1={x.to_string()} y1={(-HALF_HEIGHT).to_string()}
+                    x2={x.to_string()} y2={HALF_HEIGHT.to_string()}
+                    stroke="currentColor" class="text-secondary" stroke-width="2"
+                    vector-effect="non-scaling-stroke" />
+            }
+            // Every ply owns the strip around its point, so the chart is easy to hit
+            { for balance.iter().enumerate().map(|(index, (id, _))| {
+                let id = *id;
+                let game_ctx = game_ctx.clone();
+                let onclick = Callback::from(move |_: MouseEvent| {
+                    game_ctx.dispatch(crate::live_game::AnnotatedGameAction::GoToPly(id));
+                });
+                html! {
+                    <rect
+                        x={(index as i32 * PLY_WIDTH - PLY_WIDTH / 2).to_string()}
+                        y={(-HALF_HEIGHT).to_string()}
+                        width={PLY_WIDTH.to_string()}
+                        height={(2 * HALF_HEIGHT).to_string()}
+                        fill="transparent"
+                        {onclick} />
+                }
+            }) }
+        </svg>
+    }
+}
@@ -0,0 +1,214 @@
+use crate::models::{IllegalMove, PgnGame};
+use shady_minions::ui::{Button, Card, CardContent, CardDescription, CardHeader, CardTitle, Modal};
+use yew::prelude::*;
+
+/// Replaces the live game with `entry`, asking first if moves would be lost.
+/// Returns whether the game was replaced.
+pub fn replace_live_game(
+    game_ctx: &crate::live_game::AnnotatedGameStore,
+    entry: crate::live_game::LiveGameEntry,
+    confirm_message: &str,
+) -> bool {
+    let confirmed = game_ctx.game_tree().mainline().is_empty()
+        || web_sys::window()
+            .and_then(|window| window.confirm_with_message(confirm_message).ok())
+            .unwrap_or_default();
+    if confirmed {
+        game_ctx.dispatch(crate::live_game::AnnotatedGameAction::LoadGame(entry));
+    }
+    confirmed
+}
+
+/// Imports a game from a `.pgn` file or pasted text into the live game.
+#[function_component(ImportPgn)]
+pub fn import_pgn() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let is_open = use_state(|| false);
+    let text = use_state(String::new);
+    let games = use_state(|| None::<Vec<Result<PgnGame, IllegalMove>>>);
+
+    let toggle_open = {
+        let is_open = is_open.clone();
+        Callback::from(move |_: MouseEvent| {
+            is_open.set(!*is_open);
+        })
+    };
+    let oninput = {
+        let text = text.clone();
+        let games = games.clone();
+        Callback::from(move |e: InputEvent| {
+            text.set(
+                e.target_unchecked_into::<web_sys::HtmlTextAreaElement>()
+                    .value(),
+            );
+            games.set(None);
+        })
+    };
+    let onchange = {
+        let text = text.clone();
+        let games = games.clone();
+        Callback::from(move |e: Event| {
+            let Some(file) = e
+                .target_dyn_into::<web_sys::HtmlInputElement>()
+                .and_then(|input| input.files())
+                .and_then(|files| files.get(0))
+            else {
+                return;
+            };
+            let text = text.clone();
+            let games = games.clone();
+            yew::platform::spawn_local(async move {
+                match wasm_bindgen_futures::JsFuture::from(file.text()).await {
+                    Ok(content) => {
+                        let content = content.as_string().unwrap_or_default();
+                        games.set(Some(crate::models::read_pgn_games(&content)));
+                        text.set(content);
+                    }
+                    Err(e) => {
+                        web_sys::console::error_1(&format!("Error reading file: {:?}", e).into());
+                    }
+                }
+            });
+        })
+    };
+    let read = {
+        let text = text.clone();
+        let games = games.clone();
+        Callback::from(move |_: MouseEvent| {
+            games.set(Some(crate::models::read_pgn_games(&text)));
+        })
+    };
+    let load = {
+        let game_ctx = game_ctx.clone();
+        let is_open = is_open.clone();
+        let text = text.clone();
+        let games = games.clone();
+        let message = language_ctx.t("games_replace_current");
+        move |game: &PgnGame| {
+            let game_ctx = game_ctx.clone();
+            let is_open = is_open.clone();
+            let text = text.clone();
+            let games = games.clone();
+            let message = message.clone();
+            let entry = crate::live_game::LiveGameEntry::from_pgn_game(game);
+            Callback::from(move |_: MouseEvent| {
+                if replace_live_game(&game_ctx, entry.clone(), &message) {
+                    is_open.set(false);
+                    text.set(String::new());
+                    games.set(None);
+                }
+            })
+        }
+    };
+    let game_html = |game: &PgnGame| {
+        let header = |name: &str| game.header(name).filter(|value| *value != "?");
+        let date = header("Date").and_then(|date| {
+            crate::contexts::formatting::format_pgn_date(language_ctx.current_locale(), date)
+        });
+        html! {
+            <div class="flex flex-col overflow-hidden">
+                <span class="font-medium truncate">
+                    { format!(
+                        "{} - {}",
+                        header("White").unwrap_or_default(),
+                        header("Black").unwrap_or_default()
+                    ) }
+                </span>
+                <span class="text-xs text-muted-foreground truncate">
+                    { [header("Event").map(str::to_string), date, header("Result").map(str::to_string)]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(" · ") }
+                </span>
+            </div>
+        }
+    };
+    let games_html = match games.as_ref() {
+        None => html! {},
+        Some(games) if games.is_empty() => html! {
+            <p class="text-sm text-destructive">{ language_ctx.t("import_pgn_no_games") }</p>
+        },
+        Some(games) => games
+            .iter()
+            .map(|game| match game {
+                Ok(game) => html! {
+                    <div class="flex items-center justify-between gap-2 p-3 border border-muted rounded-md">
+                        { game_html(game) }
+                        <Button
+                            onclick={load(game)}
+                            size={shady_minions::ui::ButtonSize::Small}>
+                            { language_ctx.t("import_pgn_load") }
+                        </Button>
+                    </div>
+                },
+                // Without a valid starting position there is nothing to load
+                Err(illegal) if illegal.ply == 0 => html! {
+                    <div class="flex flex-col gap-2 p-3 border border-destructive rounded-md">
+                        { game_html(&illegal.partial) }
+                        <span class="text-xs text-destructive">
+                            { format!("{} {}", language_ctx.t("import_pgn_invalid_fen"), illegal.san) }
+                        </span>
+                    </div>
+                },
+                Err(illegal) => html! {
+                    <div class="flex flex-col gap-2 p-3 border border-destructive rounded-md">
+                        { game_html(&illegal.partial) }
+                        <span class="text-xs text-destructive">
+                            { format!(
+                                "{} {} ({} {})",
+                                language_ctx.t("import_pgn_illegal_move"),
+                                illegal.san,
+                                language_ctx.t("import_pgn_ply"),
+                                illegal.ply
+                            ) }
+                        </span>
+                        <Button
+                            onclick={load(&illegal.partial)}
+                            size={shady_minions::ui::ButtonSize::Small}
+                            variant={shady_minions::ui::ButtonVariant::Outline}>
+                            { language_ctx.t("import_pgn_load_partial") }
+                        </Button>
+                    </div>
+                },
+            })
+            .collect::<Html>(),
+    };
+
+    html! {
+        <>
+            <Button onclick={toggle_open}
+                variant={shady_minions::ui::ButtonVariant::Outline}
+                size={shady_minions::ui::ButtonSize::Icon}>
+                <lucide_yew::FileUp class="size-7" />
+            </Button>
+            <Modal {is_open}>
+                <Card class="w-full max-w-md max-h-[76vh] overflow-y-auto">
+                    <CardHeader>
+                        <CardTitle>{ language_ctx.t("import_pgn_title") }</CardTitle>
+                        <CardDescription>{ language_ctx.t("import_pgn_description") }</CardDescription>
+                    </CardHeader>
+                    <CardContent class="flex flex-col gap-3">
+                        <input
+                            type="file"
+                            accept=".pgn,text/plain"
+                            class="text-sm text-muted-foreground"
+                            {onchange} />
+                        <textarea
+                            rows="6"
+                            class="w-full rounded-md border border-input bg-background px-3 py-2 text-sm font-mono"
+                            placeholder={ language_ctx.t("import_pgn_placeholder") }
+                            value={(*text).clone()}
+                            {oninput} />
+                        <Button onclick={read} disabled={text.trim().is_empty()}>
+                            <lucide_yew::FileSearch class="size-5" />
+                            <span class="ml-2">{ language_ctx.t("import_pgn_read") }</span>
+                        </Button>
+                        { games_html }
+                    </CardContent>
+                </Card>
+            </Modal>
+        </>
+    }
+}
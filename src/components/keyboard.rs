@@ -0,0 +1,60 @@
+use web_sys::wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+/// Key that takes back the last move together with Ctrl (or Cmd).
+pub const TAKE_BACK_KEY: &str = "Backspace";
+/// Key that undoes the last edit together with Ctrl (or Cmd), with Shift it redoes it.
+pub const UNDO_KEY: &str = "z";
+/// Key that redoes the last undone edit together with Ctrl (or Cmd).
+pub const REDO_KEY: &str = "y";
+/// Key that switches between the Rookie and Expert tabs.
+pub const SWITCH_MODE_KEY: &str = "`";
+
+/// Whether the key was pressed while writing in a text field, where it
+/// belongs to the field rather than to a shortcut.
+#[must_use]
+pub fn is_typing_in_field(event: &web_sys::KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+        .is_some_and(|element| match element.tag_name().as_str() {
+            // Keypad buttons are inputs too, but they don't take text
+            "INPUT" => !matches!(
+                element.get_attribute("type").as_deref(),
+                Some("button" | "submit" | "checkbox" | "radio")
+            ),
+            "TEXTAREA" | "SELECT" => true,
+            _ => element.is_content_editable(),
+        })
+}
+
+/// Whether the element is currently displayed, hidden tabs keep their
+/// content mounted so their listeners have to check it.
+#[must_use]
+pub fn is_shown(node: &NodeRef) -> bool {
+    node.cast::<web_sys::HtmlElement>()
+        .is_some_and(|element| element.offset_parent().is_some())
+}
+
+/// Calls `callback` with every key pressed on the page while the component is mounted.
+///
+/// The listener is set up again whenever `deps` change, so the callback
+/// always sees the latest state.
+#[hook]
+pub fn use_keydown<D>(deps: D, callback: Callback<web_sys::KeyboardEvent>)
+where
+    D: PartialEq + 'static,
+{
+    use_effect_with(deps, move |_| {
+        let listener = web_sys::window()
+            .and_then(|window| window.document())
+            .map(|document| {
+                gloo::events::EventListener::new(&document, "keydown", move |event| {
+                    if let Some(event) = event.dyn_ref::<web_sys::KeyboardEvent>() {
+                        callback.emit(event.clone());
+                    }
+                })
+            });
+        move || drop(listener)
+    });
+}
@@ -1,21 +1,65 @@
+mod annotation_editor;
+mod board_setup;
+mod chess_clock;
+mod engine_analysis;
 mod expert;
+mod game_graph;
+mod import_pgn;
+mod keyboard;
 pub mod modal;
+mod move_tree;
+mod play_move_prompt;
+mod read_only_board;
+mod recipient_picker;
+mod replay_board;
+mod resume_game_prompt;
+mod session_relays;
 pub mod user_profile_card_standalone;
 
 // Re-export the UserProfileCard component
 pub use user_profile_card_standalone::UserProfileCard;
 
 mod rookie;
+pub use annotation_editor::AnnotationEditor;
+pub use board_setup::BoardSetup;
+pub use chess_clock::*;
+pub use engine_analysis::EngineAnalysis;
 pub use expert::ExpertAnnotation;
+pub use game_graph::GameGraph;
+pub use import_pgn::*;
+pub use keyboard::*;
+pub use move_tree::*;
+pub use play_move_prompt::*;
+pub use read_only_board::read_only_board;
+pub use recipient_picker::RecipientPicker;
+pub use replay_board::ReplayBoard;
+pub use resume_game_prompt::ResumeGamePrompt;
 pub use rookie::RookieAnnotation;
+pub use session_relays::use_session_relays;
 use yew::prelude::*;
 
 use nostr_minions::browser_api::IdbStoreManager;
-use shady_minions::ui::{Button, Form, Input, Popover, PopoverContent, PopoverTrigger};
+use shady_minions::ui::{Button, Popover, PopoverContent, PopoverTrigger};
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct RookyGameProps {
     pub game: rooky_core::RookyGame,
+    /// Full PGN export, including variations.
+    pub pgn: String,
+}
+
+/// Game note carrying the annotated PGN, so comments and variations survive sharing.
+fn annotated_note(game: &rooky_core::RookyGame, pgn: &str) -> nostr_minions::nostro2::NostrNote {
+    let mut note: nostr_minions::nostro2::NostrNote = game.clone().into();
+    note.content = pgn.to_string();
+    note
+}
+
+/// Kind of the notes games are published as, taken from the `RookyGame`
+/// conversion so fetching asks for exactly what sharing sends.
+#[must_use]
+pub fn game_note_kind() -> u32 {
+    annotated_note(&rooky_core::RookyGame::default(), "").kind
 }
 
 #[function_component(ShareRookyGame)]
@@ -23,30 +67,34 @@ pub fn share_rooky_game(props: &RookyGameProps) -> Html {
     let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
         .expect("Relay context not found");
     let language_ctx = crate::contexts::language::use_language_ctx();
-    let Some(keypair) = nostr_minions::key_manager::use_nostr_key() else {
+    let signer_ctx = crate::signer::use_signer();
+    let Some(signer) = signer_ctx.signer() else {
         return html! {};
     };
     let onclick = {
-        let keypair = keypair.clone();
         let game = props.game.clone();
+        let pgn = props.pgn.clone();
         let relay_ctx = relay_ctx.clone();
         Callback::from(move |_| {
-            let mut game_note: nostr_minions::nostro2::NostrNote = game.clone().into();
-            keypair
-                .sign_note(&mut game_note)
-                .expect("Failed to sign note");
-            let game_entry = rooky_core::idb::RookyGameEntry {
-                id: game_note.id.clone().unwrap_or_default(),
-                note: game_note.clone(),
-                origin: rooky_core::idb::GameOrigin::Annotated,
-            };
+            let signer = signer.clone();
+            let mut game_note = annotated_note(&game, &pgn);
+            let relay_ctx = relay_ctx.clone();
             yew::platform::spawn_local(async move {
+                if let Err(e) = signer.sign_note(&mut game_note).await {
+                    web_sys::console::error_1(&format!("Failed to sign note: {e}").into());
+                    return;
+                }
+                relay_ctx.send(game_note.clone());
+                let game_entry = rooky_core::idb::RookyGameEntry {
+                    id: game_note.id.clone().unwrap_or_default(),
+                    note: game_note,
+                    origin: rooky_core::idb::GameOrigin::Annotated,
+                };
                 game_entry
                     .save_to_store()
                     .await
                     .expect("Failed to save game");
             });
-            relay_ctx.send(game_note);
         })
     };
 
@@ -58,44 +106,48 @@ pub fn share_rooky_game(props: &RookyGameProps) -> Html {
         </Button>
     }
 }
-use nostr_minions::nostro2_signer::nostro2_nips::Nip17;
+
 #[function_component(DirectMessageRookyGame)]
 pub fn dm_rooky_game(props: &RookyGameProps) -> Html {
     let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
         .expect("Relay context not found");
     let language_ctx = crate::contexts::language::use_language_ctx();
-    let Some(keypair) = nostr_minions::key_manager::use_nostr_key() else {
+    let signer_ctx = crate::signer::use_signer();
+    let Some(signer) = signer_ctx.signer() else {
         return html! {};
     };
-    let onsubmit = {
-        let keypair = keypair.clone();
+    let onselect = {
         let game = props.game.clone();
+        let pgn = props.pgn.clone();
         let relay_ctx = relay_ctx.clone();
-        Callback::from(move |form: web_sys::HtmlFormElement| {
-            let Some(recipient) = form
-                .get_with_name("recipient")
-                .map(web_sys::wasm_bindgen::JsCast::unchecked_into::<web_sys::HtmlInputElement>)
-                .map(|input| input.value())
-            else {
-                web_sys::console::log_1(&"Recipient not found".into());
-                return;
-            };
-            let note: nostr_minions::nostro2::NostrNote = game.clone().into();
-            let note_entry = rooky_core::idb::RookyGameEntry {
-                id: note.id.clone().unwrap_or_default(),
-                note: note.clone(),
-                origin: rooky_core::idb::GameOrigin::Annotated,
-            };
+        Callback::from(move |recipient: String| {
+            let signer = signer.clone();
+            let game = game.clone();
+            let pgn = pgn.clone();
+            let relay_ctx = relay_ctx.clone();
             yew::platform::spawn_local(async move {
-                note_entry
-                    .save_to_store()
-                    .await
-                    .expect("Failed to save game");
+                let dm_game = match signer.private_dm(&pgn, &recipient).await {
+                    Ok(dm_game) => dm_game,
+                    Err(e) => {
+                        web_sys::console::error_1(&format!("Failed to seal game DM: {e}").into());
+                        return;
+                    }
+                };
+                relay_ctx.send(dm_game);
+                let mut note = annotated_note(&game, &pgn);
+                if let Err(e) = note.serialize_id() {
+                    web_sys::console::error_1(&format!("Failed to serialize ID: {e:?}").into());
+                    return;
+                }
+                let note_entry = rooky_core::idb::RookyGameEntry {
+                    id: note.id.clone().unwrap_or_default(),
+                    note,
+                    origin: rooky_core::idb::GameOrigin::Annotated,
+                };
+                if let Err(e) = note_entry.save_to_store().await {
+                    web_sys::console::error_1(&e);
+                }
             });
-            let dm_game = keypair
-                .private_dm(&game.to_pgn(), &recipient)
-                .expect("Failed to sign note");
-            relay_ctx.send(dm_game);
         })
     };
 
@@ -109,17 +161,8 @@ pub fn dm_rooky_game(props: &RookyGameProps) -> Html {
                 </div>
             </PopoverTrigger>
             <PopoverContent>
-                <Form {onsubmit} class="flex gap-2">
-                    <Input
-                        name="recipient"
-                        r#type={shady_minions::ui::InputType::Text}
-                        placeholder={ language_ctx.t("enter_recipient_nostr_id") }
-                        class={classes!("w-full", "mb-2", "min-w-32")} />
-                    <Button r#type={shady_minions::ui::ButtonType::Submit}>
-                        <lucide_yew::MessageSquareLock class={classes!("size-5")} />
-                    </Button>
-                </Form>
-                </PopoverContent>
+                <RecipientPicker {onselect} />
+            </PopoverContent>
         </Popover>
         </Button>
     }
@@ -129,13 +172,15 @@ pub fn dm_rooky_game(props: &RookyGameProps) -> Html {
 pub fn save_txt_rooky_game(props: &RookyGameProps) -> Html {
     let language_ctx = crate::contexts::language::use_language_ctx();
     let game = props.game.clone();
-    let mut note: nostr_minions::nostro2::NostrNote = game.clone().into();
+    let mut note = annotated_note(&game, &props.pgn);
     note.serialize_id().expect("Failed to serialize ID");
     let onclick = {
         let game = game.clone();
+        let pgn = props.pgn.clone();
         let id = note.id.take().unwrap();
         Callback::from(move |_| {
-            let note: nostr_minions::nostro2::NostrNote = game.clone().into();
+            let mut note = annotated_note(&game, &pgn);
+            note.serialize_id().expect("Failed to serialize ID");
             let note_entry = rooky_core::idb::RookyGameEntry {
                 id: note.id.clone().unwrap_or_default(),
                 note: note.clone(),
@@ -148,7 +193,7 @@ pub fn save_txt_rooky_game(props: &RookyGameProps) -> Html {
                     .expect("Failed to save game");
             });
             let blob_parts = web_sys::js_sys::Array::new();
-            blob_parts.push(&web_sys::wasm_bindgen::JsValue::from_str(&game.to_pgn()));
+            blob_parts.push(&web_sys::wasm_bindgen::JsValue::from_str(&pgn));
             let blob = web_sys::Blob::new_with_str_sequence(&blob_parts).unwrap();
 
             let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
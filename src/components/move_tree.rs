@@ -0,0 +1,113 @@
+use crate::models::{GameTree, MoveId, SanNotation};
+use yew::prelude::*;
+
+/// Renders the moves following `parent`, with variations nested in parentheses.
+pub fn line_html(
+    tree: &GameTree,
+    parent: MoveId,
+    force_number: bool,
+    highlight: Option<MoveId>,
+    onselect: &Callback<MoveId>,
+    notation: SanNotation,
+) -> Html {
+    let mut items = vec![];
+    let mut current = parent;
+    let mut force_number = force_number;
+    while let Some((main, variations)) = tree.children(current).split_first() {
+        items.push(move_token_html(
+            tree,
+            *main,
+            force_number,
+            highlight,
+            onselect,
+            notation,
+        ));
+        for variation in variations {
+            items.push(variation_html(
+                tree, *variation, highlight, onselect, notation,
+            ));
+        }
+        force_number = !variations.is_empty();
+        current = *main;
+    }
+    items.into_iter().collect::<Html>()
+}
+
+/// Renders a single variation starting with `id`, wrapped in parentheses.
+pub fn variation_html(
+    tree: &GameTree,
+    id: MoveId,
+    highlight: Option<MoveId>,
+    onselect: &Callback<MoveId>,
+    notation: SanNotation,
+) -> Html {
+    html! {
+        <span class="inline-flex items-center gap-1 text-xs text-muted-foreground">
+            <span>{"("}</span>
+            { move_token_html(tree, id, true, highlight, onselect, notation) }
+            { line_html(tree, id, false, highlight, onselect, notation) }
+            <span>{")"}</span>
+        </span>
+    }
+}
+
+pub fn move_token_html(
+    tree: &GameTree,
+    id: MoveId,
+    force_number: bool,
+    highlight: Option<MoveId>,
+    onselect: &Callback<MoveId>,
+    notation: SanNotation,
+) -> Html {
+    let Some(node) = tree.get(id).cloned() else {
+        return html! {};
+    };
+    let san = node
+        .san
+        .as_ref()
+        .map(|san| notation.display(san))
+        .unwrap_or_default();
+    let onclick = {
+        let onselect = onselect.clone();
+        Callback::from(move |_: MouseEvent| onselect.emit(id))
+    };
+    let class = classes!(
+        "inline-flex",
+        "items-center",
+        "gap-1",
+        "rounded",
+        "px-1",
+        "cursor-pointer",
+        if highlight == Some(id) {
+            "bg-secondary text-white font-semibold"
+        } else {
+            "hover:bg-muted"
+        }
+    );
+    html! {
+        <span {class} {onclick} data-current-move={(highlight == Some(id)).then_some("true")}>
+            { if let Some(prefix) = tree.move_number_prefix(id, force_number) {
+                html! { <span class="text-secondary-foreground">{ prefix }</span> }
+            } else {
+                html! {}
+            }}
+            <span>{ san }</span>
+            { node.nags.iter().map(|nag| html! {
+                <span class="text-primary">{ crate::models::nag_glyph(*nag) }</span>
+            }).collect::<Html>() }
+            if let Some(comment) = node.comment {
+                <span class="italic font-normal text-muted-foreground">{ comment }</span>
+            }
+        </span>
+    }
+}
+
+/// Renders the comment written before the first move, if any.
+pub fn pre_game_comment_html(tree: &GameTree) -> Html {
+    tree.get(GameTree::ROOT)
+        .and_then(|root| root.comment.clone())
+        .map_or_else(
+            || html! {},
+            |comment| html! { <span class="italic text-muted-foreground">{ comment }</span> },
+        )
+}
@@ -0,0 +1,95 @@
+use shady_minions::ui::{Button, Card, CardContent, CardDescription, CardHeader, CardTitle, Modal};
+use yew::prelude::*;
+
+/// Plays `mv` at the cursor, or stores it in `pending` when the cursor is in the
+/// middle of a line and the user has to pick how to insert it.
+pub fn play_or_prompt(
+    game_ctx: &crate::live_game::AnnotatedGameStore,
+    pending: &UseStateHandle<Option<shakmaty::Move>>,
+    mv: shakmaty::Move,
+) {
+    if game_ctx.needs_insert_choice(&mv) {
+        pending.set(Some(mv));
+    } else {
+        game_ctx.dispatch(crate::live_game::AnnotatedGameAction::PlayMove(mv));
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct PlayMovePromptProps {
+    pub pending: UseStateHandle<Option<shakmaty::Move>>,
+}
+
+#[function_component(PlayMovePrompt)]
+pub fn play_move_prompt(props: &PlayMovePromptProps) -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let is_open = use_state(|| false);
+
+    {
+        let is_open = is_open.clone();
+        use_effect_with(props.pending.is_some(), move |has_pending| {
+            is_open.set(*has_pending);
+            || {}
+        });
+    }
+    // Closing the modal discards the pending move
+    {
+        let pending = props.pending.clone();
+        use_effect_with(*is_open, move |open| {
+            if !*open {
+                pending.set(None);
+            }
+            || {}
+        });
+    }
+
+    let resolve = |overwrite: bool| {
+        let game_ctx = game_ctx.clone();
+        let pending = props.pending.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(mv) = (*pending).clone() {
+                game_ctx.dispatch(if overwrite {
+                    crate::live_game::AnnotatedGameAction::OverwriteMove(mv)
+                } else {
+                    crate::live_game::AnnotatedGameAction::PlayMove(mv)
+                });
+            }
+            pending.set(None);
+        })
+    };
+    let notation = crate::configs::use_annotator_config().san_notation();
+    let san = props
+        .pending
+        .as_ref()
+        .map(|mv| {
+            notation.display(&shakmaty::san::SanPlus::from_move(
+                game_ctx.cursor_position(),
+                mv,
+            ))
+        })
+        .unwrap_or_default();
+
+    html! {
+        <Modal {is_open}>
+            <Card class="w-full max-w-sm">
+                <CardHeader>
+                    <CardTitle>{ format!("{} {san}", language_ctx.t("insert_move_title")) }</CardTitle>
+                    <CardDescription>{ language_ctx.t("insert_move_description") }</CardDescription>
+                </CardHeader>
+                <CardContent class="flex flex-col gap-2">
+                    <Button onclick={resolve(false)}>
+                        <lucide_yew::GitBranch class="size-5" />
+                        <span class="ml-2">{ language_ctx.t("insert_move_variation") }</span>
+                    </Button>
+                    <Button
+                        onclick={resolve(true)}
+                        variant={shady_minions::ui::ButtonVariant::Destructive}>
+                        <lucide_yew::Eraser class="size-5" />
+                        <span class="ml-2">{ language_ctx.t("insert_move_overwrite") }</span>
+                    </Button>
+                </CardContent>
+            </Card>
+        </Modal>
+    }
+}
@@ -0,0 +1,17 @@
+/// Draws the position of `fen` on the element with id `board_id`, with
+/// pieces that can't be dragged.
+pub fn read_only_board(
+    board_id: &str,
+    fen: &str,
+    orientation: chessboard_js::ChessboardOrientation,
+) -> chessboard_js::ChessBoardJs {
+    // Crazyhouse pockets follow the board in brackets
+    let board_fen = fen.split([' ', '[']).next().unwrap_or_default();
+    let board_options = chessboard_js::ChessboardConfig {
+        draggable: false,
+        position: chessboard_js::ChessboardPosition::Fen(board_fen.to_string()),
+        orientation,
+        ..Default::default()
+    };
+    chessboard_js::ChessBoardJs::new(board_id, Some(board_options))
+}
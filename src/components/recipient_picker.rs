@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use crate::models::{
+    followed_pubkeys, resolve_recipient, NostrMetadata, RecentRecipientsEntry, RecipientError,
+    WellKnownLookup,
+};
+use nostr_minions::browser_api::IdbStoreManager;
+use shady_minions::ui::Button;
+use yew::prelude::*;
+
+/// Kind of the contact list, whose `p` tags are the follows.
+const CONTACT_LIST_KIND: u32 = 3;
+/// Suggestions shown under the recipient input.
+const SUGGESTION_LIMIT: usize = 5;
+
+/// Short form of a pubkey for when its profile isn't known.
+fn short_pubkey(pubkey: &str) -> String {
+    format!(
+        "{}…{}",
+        pubkey.get(..8).unwrap_or_default(),
+        pubkey
+            .get(pubkey.len().saturating_sub(4)..)
+            .unwrap_or_default()
+    )
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct RecipientPickerProps {
+    /// Called with the hex pubkey once the user confirms the recipient.
+    pub onselect: Callback<String>,
+}
+
+/// Recipient input taking an npub, hex pubkey, nprofile or NIP-05 identifier,
+/// with suggestions from the recently messaged pubkeys and the follow list.
+///
+/// The chosen recipient is shown with their profile before it's confirmed.
+#[function_component(RecipientPicker)]
+pub fn recipient_picker(props: &RecipientPickerProps) -> Html {
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
+        .expect("Relay context not found");
+    let own_pubkey = crate::signer::use_signer().pubkey();
+    let query = use_state(String::new);
+    let resolved = use_state(|| None::<Result<String, RecipientError>>);
+    let resolving = use_state(|| false);
+    let sent = use_state(|| false);
+    let recent = use_state(RecentRecipientsEntry::default);
+
+    use_effect_with((), {
+        let recent = recent.clone();
+        move |()| {
+            yew::platform::spawn_local(async move {
+                if let Ok(entry) =
+                    RecentRecipientsEntry::retrieve_from_store::<RecentRecipientsEntry>(
+                        &RecentRecipientsEntry::ID.into(),
+                    )
+                    .await
+                {
+                    recent.set(entry);
+                }
+            });
+            || {}
+        }
+    });
+    {
+        let relay_ctx = relay_ctx.clone();
+        use_effect_with(own_pubkey.clone(), move |pubkey| {
+            if let Some(pubkey) = pubkey {
+                relay_ctx.send(nostr_minions::nostro2::NostrSubscription {
+                    kinds: Some(vec![CONTACT_LIST_KIND]),
+                    authors: Some(vec![pubkey.clone()]),
+                    limit: Some(1),
+                    ..Default::default()
+                });
+            }
+            || {}
+        });
+    }
+
+    // Latest contact list and profiles among the notes received
+    let follows = relay_ctx
+        .unique_notes
+        .iter()
+        .filter(|note| {
+            note.kind == CONTACT_LIST_KIND
+                && own_pubkey
+                    .as_ref()
+                    .is_some_and(|pubkey| *pubkey == note.pubkey)
+        })
+        .max_by_key(|note| note.created_at)
+        .map(followed_pubkeys)
+        .unwrap_or_default();
+    let mut profiles = HashMap::<String, (i64, NostrMetadata)>::new();
+    for note in relay_ctx.unique_notes.iter().filter(|note| note.kind == 0) {
+        if profiles
+            .get(&note.pubkey)
+            .is_some_and(|(known, _)| *known >= note.created_at)
+        {
+            continue;
+        }
+        if let Ok(metadata) = NostrMetadata::try_from(note.clone()) {
+            profiles.insert(note.pubkey.clone(), (note.created_at, metadata));
+        }
+    }
+    let mut candidates = recent
+        .pubkeys
+        .iter()
+        .chain(&follows)
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut seen = std::collections::HashSet::new();
+    candidates.retain(|pubkey| seen.insert(pubkey.clone()));
+
+    // Profiles of the candidates, and of a recipient typed in full
+    {
+        let relay_ctx = relay_ctx.clone();
+        let mut authors = candidates.clone();
+        if let Some(Ok(pubkey)) = &*resolved {
+            if !authors.contains(pubkey) {
+                authors.push(pubkey.clone());
+            }
+        }
+        use_effect_with(authors, move |authors| {
+            if !authors.is_empty() {
+                relay_ctx.send(nostr_minions::nostro2::NostrSubscription {
+                    kinds: Some(vec![0]),
+                    authors: Some(authors.clone()),
+                    ..Default::default()
+                });
+            }
+            || {}
+        });
+    }
+
+    let oninput = {
+        let query = query.clone();
+        let resolved = resolved.clone();
+        let sent = sent.clone();
+        Callback::from(move |e: InputEvent| {
+            query.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            );
+            resolved.set(None);
+            sent.set(false);
+        })
+    };
+    let onsubmit = {
+        let query = query.clone();
+        let resolved = resolved.clone();
+        let resolving = resolving.clone();
+        Callback::from(move |_: web_sys::HtmlFormElement| {
+            let query = (*query).clone();
+            let resolved = resolved.clone();
+            let resolving = resolving.clone();
+            resolving.set(true);
+            yew::platform::spawn_local(async move {
+                resolved.set(Some(resolve_recipient(&query, &WellKnownLookup).await));
+                resolving.set(false);
+            });
+        })
+    };
+    let pick = |pubkey: String| {
+        let resolved = resolved.clone();
+        let sent = sent.clone();
+        Callback::from(move |_: MouseEvent| {
+            resolved.set(Some(Ok(pubkey.clone())));
+            sent.set(false);
+        })
+    };
+    let confirm = {
+        let onselect = props.onselect.clone();
+        let sent = sent.clone();
+        let recent = recent.clone();
+        move |pubkey: String| {
+            let onselect = onselect.clone();
+            let sent = sent.clone();
+            let recent = recent.clone();
+            Callback::from(move |_: MouseEvent| {
+                onselect.emit(pubkey.clone());
+                sent.set(true);
+                let entry = (*recent).clone().with_recipient(&pubkey);
+                recent.set(entry.clone());
+                yew::platform::spawn_local(async move {
+                    if let Err(e) = entry.save_to_store().await {
+                        web_sys::console::error_1(&e);
+                    }
+                });
+            })
+        }
+    };
+
+    let profile_row = |pubkey: &str| {
+        let metadata = profiles.get(pubkey).map(|(_, metadata)| metadata);
+        let picture = metadata
+            .and_then(|metadata| metadata.picture.clone())
+            .unwrap_or_else(|| "/public/assets/img/default-avatar.png".to_string());
+        let name = metadata.map_or_else(|| short_pubkey(pubkey), |metadata| metadata.name.clone());
+        html! {
+            <div class="flex items-center gap-2 overflow-hidden">
+                <img src={picture} alt={name.clone()} class="size-6 rounded-full object-cover shrink-0" />
+                <span class="text-sm truncate">{ name }</span>
+            </div>
+        }
+    };
+    let search = query.trim().to_lowercase();
+    let suggestions = candidates
+        .iter()
+        .filter(|pubkey| {
+            search.is_empty()
+                || pubkey.starts_with(&search)
+                || profiles
+                    .get(*pubkey)
+                    .is_some_and(|(_, metadata)| metadata.name.to_lowercase().contains(&search))
+        })
+        .take(SUGGESTION_LIMIT)
+        .collect::<Vec<_>>();
+    let error_text = |error: &RecipientError| match error {
+        RecipientError::Empty => language_ctx.t("recipient_error_empty"),
+        RecipientError::Unrecognized => language_ctx.t("recipient_error_unrecognized"),
+        RecipientError::UnknownName => language_ctx.t("recipient_error_unknown_name"),
+        RecipientError::Lookup(_) => language_ctx.t("recipient_error_lookup"),
+    };
+
+    html! {
+        <div class="flex flex-col gap-2 min-w-56">
+            <shady_minions::ui::Form {onsubmit} class="flex gap-2">
+                <input
+                    name="recipient"
+                    type="text"
+                    autocomplete="off"
+                    class="w-full rounded-md border border-input bg-background px-3 py-2 text-sm"
+                    placeholder={ language_ctx.t("enter_recipient_nostr_id") }
+                    value={(*query).clone()}
+                    {oninput} />
+                <Button
+                    r#type={shady_minions::ui::ButtonType::Submit}
+                    disabled={*resolving || query.trim().is_empty()}>
+                    <lucide_yew::Search class="size-5" />
+                </Button>
+            </shady_minions::ui::Form>
+            {match &*resolved {
+                Some(Ok(pubkey)) => html! {
+                    <div class="flex items-center justify-between gap-2 p-2 border border-muted rounded-md">
+                        { profile_row(pubkey) }
+                        if *sent {
+                            <lucide_yew::Check class="size-5 text-secondary" />
+                        } else {
+                            <Button
+                                onclick={confirm(pubkey.clone())}
+                                size={shady_minions::ui::ButtonSize::Small}>
+                                <lucide_yew::Send class="size-4" />
+                                <span class="ml-2">{ language_ctx.t("recipient_send") }</span>
+                            </Button>
+                        }
+                    </div>
+                },
+                Some(Err(error)) => html! {
+                    <p class="text-sm text-destructive">{ error_text(error) }</p>
+                },
+                None => html! {
+                    <div class="flex flex-col gap-1">
+                        { for suggestions.into_iter().map(|pubkey| html! {
+                            <button
+                                type="button"
+                                class="p-1 rounded-md hover:bg-muted text-left"
+                                onclick={pick(pubkey.clone())}>
+                                { profile_row(pubkey) }
+                            </button>
+                        }) }
+                    </div>
+                },
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,135 @@
+use shady_minions::ui::Button;
+use yew::prelude::*;
+
+/// Time each position is shown while the game plays itself, in milliseconds.
+const AUTOPLAY_STEP_MS: u32 = 1000;
+
+/// Board following the move cursor of the game, to replay it move by move.
+///
+/// Autoplay steps along the line the cursor is on and stops at its last move.
+#[function_component(ReplayBoard)]
+pub fn replay_board() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let configs = crate::configs::use_annotator_config();
+    let board_ref = use_node_ref();
+    let board_id = "replay-board";
+    let game_board = use_mut_ref(|| None::<chessboard_js::ChessBoardJs>);
+    let flipped = use_state(|| false);
+    let playing = use_state(|| false);
+    let cursor = game_ctx.cursor();
+    let at_line_end = game_ctx.game_tree().children(cursor).is_empty();
+    let fen = shakmaty::fen::Fen::from_position(
+        game_ctx.cursor_position(),
+        shakmaty::EnPassantMode::Legal,
+    )
+    .to_string();
+    let white_below =
+        matches!(configs.playing_as, crate::configs::BoardPlayingSide::White) != *flipped;
+
+    {
+        let board_setting = game_board.clone();
+        use_effect_with(
+            (board_ref.clone(), fen, white_below),
+            move |(_, fen, white_below)| {
+                let orientation = if *white_below {
+                    chessboard_js::ChessboardOrientation::White
+                } else {
+                    chessboard_js::ChessboardOrientation::Black
+                };
+                let board = super::read_only_board(board_id, fen, orientation);
+                *board_setting.borrow_mut() = Some(board);
+                || {}
+            },
+        );
+    }
+    {
+        let game_ctx = game_ctx.clone();
+        let playing = playing.clone();
+        use_effect_with((*playing, cursor), move |(is_playing, _)| {
+            let step = (*is_playing).then(|| {
+                if at_line_end {
+                    playing.set(false);
+                    return None;
+                }
+                Some(gloo::timers::callback::Timeout::new(
+                    AUTOPLAY_STEP_MS,
+                    move || {
+                        game_ctx.dispatch(crate::live_game::AnnotatedGameAction::StepForward);
+                    },
+                ))
+            });
+            move || drop(step)
+        });
+    }
+
+    let dispatch = {
+        let game_ctx = game_ctx.clone();
+        move |action: fn() -> crate::live_game::AnnotatedGameAction| {
+            let game_ctx = game_ctx.clone();
+            Callback::from(move |_: MouseEvent| game_ctx.dispatch(action()))
+        }
+    };
+    let toggle_playing = {
+        let playing = playing.clone();
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            // Playing from the end starts over
+            if !*playing && at_line_end {
+                game_ctx.dispatch(crate::live_game::AnnotatedGameAction::GoToStart);
+            }
+            playing.set(!*playing);
+        })
+    };
+    let flip = {
+        let flipped = flipped.clone();
+        Callback::from(move |_: MouseEvent| flipped.set(!*flipped))
+    };
+
+    html! {
+        <div class="flex flex-col gap-2 w-full max-w-xs mx-auto mb-6">
+            <div ref={board_ref} id={board_id} class="w-full aspect-square" />
+            <div class="flex gap-1 justify-center items-center">
+                <Button
+                    onclick={dispatch(|| crate::live_game::AnnotatedGameAction::GoToStart)}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::ChevronsLeft class="size-4" />
+                </Button>
+                <Button
+                    onclick={dispatch(|| crate::live_game::AnnotatedGameAction::StepBack)}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::ChevronLeft class="size-4" />
+                </Button>
+                <Button
+                    onclick={toggle_playing}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    if *playing {
+                        <lucide_yew::Pause class="size-4" />
+                    } else {
+                        <lucide_yew::Play class="size-4" />
+                    }
+                </Button>
+                <Button
+                    onclick={dispatch(|| crate::live_game::AnnotatedGameAction::StepForward)}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::ChevronRight class="size-4" />
+                </Button>
+                <Button
+                    onclick={dispatch(|| crate::live_game::AnnotatedGameAction::GoToEnd)}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::ChevronsRight class="size-4" />
+                </Button>
+                <Button
+                    onclick={flip}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::ArrowUpDown class="size-4" />
+                </Button>
+            </div>
+        </div>
+    }
+}
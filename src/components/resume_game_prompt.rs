@@ -0,0 +1,69 @@
+use shady_minions::ui::{Button, Card, CardContent, CardDescription, CardHeader, CardTitle, Modal};
+use yew::prelude::*;
+
+/// Asks whether to resume the unfinished game found in storage on startup.
+#[function_component(ResumeGamePrompt)]
+pub fn resume_game_prompt() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let is_open = use_state(|| false);
+
+    {
+        let is_open = is_open.clone();
+        use_effect_with(game_ctx.saved_game().is_some(), move |has_saved| {
+            is_open.set(*has_saved);
+            || {}
+        });
+    }
+    // Dismissing the modal keeps the game, only the discard button throws it away
+    {
+        let game_ctx = game_ctx.clone();
+        use_effect_with(*is_open, move |open| {
+            if !*open && game_ctx.saved_game().is_some() {
+                game_ctx.dispatch(crate::live_game::AnnotatedGameAction::ResumeSavedGame);
+            }
+            || {}
+        });
+    }
+
+    let dispatch = |action: fn() -> crate::live_game::AnnotatedGameAction| {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| game_ctx.dispatch(action()))
+    };
+    let description = game_ctx
+        .saved_game()
+        .map(|entry| {
+            let players = match (entry.white.is_empty(), entry.black.is_empty()) {
+                (true, true) => String::new(),
+                _ => format!("{} - {}, ", entry.white, entry.black),
+            };
+            format!(
+                "{players}{}",
+                language_ctx.tn("resume_game_moves", entry.tree.mainline_len() as u64)
+            )
+        })
+        .unwrap_or_default();
+
+    html! {
+        <Modal {is_open}>
+            <Card class="w-full max-w-sm">
+                <CardHeader>
+                    <CardTitle>{ language_ctx.t("resume_game_title") }</CardTitle>
+                    <CardDescription>{ description }</CardDescription>
+                </CardHeader>
+                <CardContent class="flex flex-col gap-2">
+                    <Button onclick={dispatch(|| crate::live_game::AnnotatedGameAction::ResumeSavedGame)}>
+                        <lucide_yew::History class="size-5" />
+                        <span class="ml-2">{ language_ctx.t("resume_game_resume") }</span>
+                    </Button>
+                    <Button
+                        onclick={dispatch(|| crate::live_game::AnnotatedGameAction::DiscardSavedGame)}
+                        variant={shady_minions::ui::ButtonVariant::Destructive}>
+                        <lucide_yew::Trash2 class="size-5" />
+                        <span class="ml-2">{ language_ctx.t("resume_game_discard") }</span>
+                    </Button>
+                </CardContent>
+            </Card>
+        </Modal>
+    }
+}
@@ -5,8 +5,27 @@ pub fn rookie_annotation() -> Html {
     let next_move = use_state(|| None::<shakmaty::Move>);
     let next_role = use_state(|| None::<shakmaty::Role>);
     let next_from_square = use_state(|| None::<shakmaty::Square>);
+    // Crazyhouse pieces dropped from the pocket have no from square
+    let is_drop = use_state(|| false);
+    let pending_move = use_state(|| None::<shakmaty::Move>);
     let language_ctx = crate::contexts::language::use_language_ctx();
     let game_ctx = crate::live_game::use_annotated_game();
+    let notation = crate::configs::use_annotator_config().san_notation();
+
+    // Start the selection over whenever the cursor moves to another position
+    {
+        let next_move = next_move.clone();
+        let next_role = next_role.clone();
+        let next_from_square = next_from_square.clone();
+        let is_drop = is_drop.clone();
+        use_effect_with(game_ctx.cursor(), move |_| {
+            next_move.set(None);
+            next_role.set(None);
+            next_from_square.set(None);
+            is_drop.set(false);
+            || {}
+        });
+    }
 
     let clear_role = {
         let next_role = next_role.clone();
@@ -20,9 +39,11 @@ pub fn rookie_annotation() -> Html {
     let clear_from_square = {
         let next_from_square = next_from_square.clone();
         let next_move = next_move.clone();
+        let is_drop = is_drop.clone();
         Callback::from(move |_| {
             next_from_square.set(None);
             next_move.set(None);
+            is_drop.set(false);
         })
     };
 
@@ -33,8 +54,17 @@ pub fn rookie_annotation() -> Html {
         })
     };
 
-    let back_option = match (next_role.as_ref(), next_from_square.as_ref()) {
-        (Some(_), Some(_)) => html! {
+    let set_drop = {
+        let is_drop = is_drop.clone();
+        Callback::from(move |()| {
+            is_drop.set(true);
+        })
+    };
+
+    // A drop skips the from square, going back returns to its selection all the same
+    let has_from_square = next_from_square.is_some() || *is_drop;
+    let back_option = match (next_role.as_ref(), has_from_square) {
+        (Some(_), true) => html! {
             <shady_minions::ui::Button
                 size={shady_minions::ui::ButtonSize::Icon}
                 onclick={clear_from_square}>
@@ -42,7 +72,7 @@ pub fn rookie_annotation() -> Html {
                     class="size-6" />
             </shady_minions::ui::Button>
         },
-        (Some(_), None) => html! {
+        (Some(_), false) => html! {
             <shady_minions::ui::Button
                 size={shady_minions::ui::ButtonSize::Icon}
                 onclick={clear_role}>
@@ -54,11 +84,11 @@ pub fn rookie_annotation() -> Html {
                 <div class="w-2"></div> // Spacer for centering
         },
     };
-    let inner_html = match (next_role.as_ref(), next_from_square.as_ref()) {
-        (Some(role), None) => html! {
-            <FromSquareSelection piece={*role} onclick={set_next_from_square} />
+    let inner_html = match (next_role.as_ref(), has_from_square) {
+        (Some(role), false) => html! {
+            <FromSquareSelection piece={*role} onclick={set_next_from_square} ondrop={set_drop} />
         },
-        (Some(_), Some(_)) => html! {
+        (Some(_), true) => html! {
             <MoveSelection
                 next_move={next_move.clone()}
                 next_role={next_role.clone()}
@@ -90,15 +120,21 @@ pub fn rookie_annotation() -> Html {
                 { format!("{:?}", square) }
             </h3>
         }
+    } else if *is_drop {
+        html! {
+            <h3 class="text-center size-6 p-1 font-bold text-muted">
+                { "@" }
+            </h3>
+        }
     } else {
         html! {}
     };
 
     let move_html = if let Some(mv) = next_move.as_ref() {
-        let san_move = shakmaty::san::SanPlus::from_move(game_ctx.last_game_position(), mv);
+        let san_move = shakmaty::san::SanPlus::from_move(game_ctx.cursor_position(), mv);
         html! {
             <h3 class="text-center size-6 p-1 font-bold text-muted">
-                { format!("{san_move}") }
+                { notation.display(&san_move) }
             </h3>
         }
     } else {
@@ -114,17 +150,17 @@ pub fn rookie_annotation() -> Html {
                     <div class="p-1">
                         <div class="grid grid-cols-3 gap-1">
                             <div class="text-center">
-                                <label class="text-xs font-medium text-foreground block">{"Role"}</label>
+                                <label class="text-xs font-medium text-foreground block">{ language_ctx.t("annotation_role") }</label>
                                 {role_html}
                             </div>
 
                             <div class="text-center items-center flex flex-col">
-                                <label class="text-xs font-medium text-foreground block">{"From Square"}</label>
+                                <label class="text-xs font-medium text-foreground block">{ language_ctx.t("annotation_from_square") }</label>
                                 {next_from_square_html}
                             </div>
 
                             <div class="text-center items-center flex flex-col">
-                                <label class="text-xs font-medium text-foreground block">{"Move"}</label>
+                                <label class="text-xs font-medium text-foreground block">{ language_ctx.t("annotation_move") }</label>
                                 {move_html}
                             </div>
                         </div>
@@ -156,12 +192,15 @@ pub fn rookie_annotation() -> Html {
                     <PlayMoveButton
                         next_move={next_move.clone()}
                         next_role={next_role.clone()}
-                        next_from_square={next_from_square.clone()} />
+                        next_from_square={next_from_square.clone()}
+                        is_drop={is_drop.clone()}
+                        pending_move={pending_move.clone()} />
                 }
             } else {
                 html! {
                 }
             }}
+            <crate::components::PlayMovePrompt pending={pending_move} />
         </div>
     }
 }
@@ -296,6 +335,8 @@ pub fn piece_selector(props: &PieceSelectorProps) -> Html {
 pub struct FromSquareSelectionProps {
     pub piece: shakmaty::Role,
     pub onclick: Callback<shakmaty::Square>,
+    /// Chooses to drop the piece from the pocket instead of moving one on the board.
+    pub ondrop: Callback<()>,
 }
 
 #[function_component(FromSquareSelection)]
@@ -308,7 +349,11 @@ pub fn from_square_selection(props: &FromSquareSelectionProps) -> Html {
     };
     let board_ref = use_node_ref();
     let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
     let legal_moves = game_ctx.legal_moves();
+    let can_drop = legal_moves
+        .iter()
+        .any(|m| m.role() == props.piece && m.from().is_none());
     let from_squares = legal_moves
         .iter()
         .filter_map(|m| (m.role() == props.piece).then(|| m.from()).flatten())
@@ -379,8 +424,21 @@ pub fn from_square_selection(props: &FromSquareSelectionProps) -> Html {
             },
         );
     }
+    let ondrop = {
+        let ondrop = props.ondrop.clone();
+        Callback::from(move |_: MouseEvent| ondrop.emit(()))
+    };
     html! {
-        <div ref={board_ref} id={board_id} class="h-[35vh] sm:h-[45vh] aspect-square mx-auto" />
+        <div class="flex flex-col gap-3">
+            <div ref={board_ref} id={board_id} class="h-[35vh] sm:h-[45vh] aspect-square mx-auto" />
+            if can_drop {
+                <shady_minions::ui::Button
+                    onclick={ondrop}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    { language_ctx.t("annotation_drop_piece") }
+                </shady_minions::ui::Button>
+            }
+        </div>
     }
 }
 
@@ -396,6 +454,7 @@ pub fn move_selection(props: &MoveSelectionProps) -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
     let next_move = props.next_move.clone();
     let legal_moves = game_ctx.legal_moves();
+    // Drops have no from square, so they are the moves left when none is selected
     let legal_moves = legal_moves
         .iter()
         .filter(|m| {
@@ -405,16 +464,117 @@ pub fn move_selection(props: &MoveSelectionProps) -> Html {
         .cloned()
         .collect::<Vec<_>>();
 
+    // Square with several promotion moves, waiting for the piece to be chosen
+    let promotion_square = use_state(|| None::<shakmaty::Square>);
+
     // Storing the moves in a UseState hook for immutability
     let on_select = {
+        let next_move = next_move.clone();
+        let promotion_square = promotion_square.clone();
+        let legal_moves = legal_moves.clone();
+        Callback::from(move |m: shakmaty::Move| {
+            let promotions = legal_moves
+                .iter()
+                .filter(|other| other.to() == m.to() && other.promotion().is_some())
+                .count();
+            if promotions > 1 {
+                promotion_square.set(Some(m.to()));
+                next_move.set(None);
+            } else {
+                promotion_square.set(None);
+                next_move.set(Some(m));
+            }
+        })
+    };
+    let on_promotion = {
         let next_move = next_move.clone();
         Callback::from(move |m: shakmaty::Move| {
             next_move.set(Some(m));
         })
     };
+    let promotion_moves = promotion_square
+        .as_ref()
+        .map(|square| {
+            legal_moves
+                .iter()
+                .filter(|m| m.to() == *square && m.promotion().is_some())
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    html! {
+        <div class="flex flex-col gap-3">
+            <MultiSquaresPreview  next_move={(*next_move).clone()}
+                moves={legal_moves.clone()} on_select={on_select.clone()} />
+            if !promotion_moves.is_empty() {
+                <PromotionChooser
+                    moves={promotion_moves}
+                    selected={(*next_move).clone()}
+                    on_select={on_promotion} />
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PromotionChooserProps {
+    /// Moves to the same square that only differ by the promoted piece.
+    pub moves: Vec<shakmaty::Move>,
+    pub selected: Option<shakmaty::Move>,
+    pub on_select: Callback<shakmaty::Move>,
+}
+
+#[function_component(PromotionChooser)]
+pub fn promotion_chooser(props: &PromotionChooserProps) -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let color = game_ctx.color_turn();
+
     html! {
-        <MultiSquaresPreview  next_move={(*next_move).clone()}
-            moves={legal_moves.clone()} on_select={on_select.clone()} />
+        <div class="flex flex-col gap-2">
+            <span class="text-sm text-center font-medium text-foreground">
+                { language_ctx.t("annotation_promote_to") }
+            </span>
+            <div class="flex gap-2 justify-center">
+                { for props.moves.iter().filter_map(|m| {
+                    let role = m.promotion()?;
+                    let is_selected = props.selected.as_ref() == Some(m);
+                    let onclick = {
+                        let on_select = props.on_select.clone();
+                        let m = m.clone();
+                        Callback::from(move |_: MouseEvent| on_select.emit(m.clone()))
+                    };
+                    Some(html! {
+                        <button
+                            {onclick}
+                            class={classes!(
+                                match color {
+                                    shakmaty::Color::White => "bg-white",
+                                    shakmaty::Color::Black => "bg-black",
+                                },
+                                if is_selected {
+                                    "ring-4 ring-primary"
+                                } else {
+                                    "hover:bg-secondary"
+                                },
+                                "p-2",
+                                "rounded",
+                                "aspect-square",
+                                "size-16",
+                                "flex",
+                                "items-center",
+                                "justify-center",
+                                "cursor-pointer"
+                            )}>
+                            <img
+                                src={format!("/public/assets/img/{}{}.svg", color.char(), role.upper_char())}
+                                alt={format!("{:?}", role)}
+                                class="size-10 object-cover" />
+                        </button>
+                    })
+                }) }
+            </div>
+        </div>
     }
 }
 
@@ -529,6 +689,8 @@ pub struct PlayMoveButtonProps {
     pub next_move: UseStateHandle<Option<shakmaty::Move>>,
     pub next_role: UseStateHandle<Option<shakmaty::Role>>,
     pub next_from_square: UseStateHandle<Option<shakmaty::Square>>,
+    pub is_drop: UseStateHandle<bool>,
+    pub pending_move: UseStateHandle<Option<shakmaty::Move>>,
 }
 
 #[function_component(PlayMoveButton)]
@@ -538,18 +700,22 @@ pub fn play_move_button(props: &PlayMoveButtonProps) -> Html {
         next_move,
         next_role,
         next_from_square,
+        is_drop,
+        pending_move,
     } = props.clone();
     let language_ctx = crate::contexts::language::use_language_ctx();
     let onclick = {
         let game_ctx = game_ctx.clone();
         let next_move = next_move.clone();
+        let pending_move = pending_move.clone();
         Callback::from(move |_| {
             if let Some(m) = next_move.as_ref() {
-                game_ctx.dispatch(crate::live_game::AnnotatedGameAction::PlayMove(m.clone()));
+                crate::components::play_or_prompt(&game_ctx, &pending_move, m.clone());
             }
             next_move.set(None);
             next_role.set(None);
             next_from_square.set(None);
+            is_drop.set(false);
         })
     };
     html! {
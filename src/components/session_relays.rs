@@ -0,0 +1,41 @@
+use nostr_minions::browser_api::IdbStoreManager;
+use nostr_minions::relay_pool::{NostrRelayPoolAction, UserRelay};
+use yew::prelude::*;
+
+/// Connects the relay pool to `relays` while the component is mounted,
+/// leaving the user's own relay list as it was.
+///
+/// Relays already in the pool are left alone. The others are removed again
+/// once `relays` change or the component goes away, from the pool and from
+/// the saved list the pool adds them to.
+#[hook]
+pub fn use_session_relays(relays: Vec<String>, write: bool) {
+    let relay_pool = nostr_minions::relay_pool::use_nostr_relay_pool();
+    use_effect_with((relays, write), move |(relays, write)| {
+        let known = relay_pool.relay_health();
+        let added = relays
+            .iter()
+            .filter(|relay| !known.contains_key(relay.as_str()))
+            .map(|relay| UserRelay {
+                url: relay.clone(),
+                read: true,
+                write: *write,
+            })
+            .collect::<Vec<_>>();
+        for relay in &added {
+            relay_pool.dispatch(NostrRelayPoolAction::AddRelay(relay.clone()));
+        }
+        move || {
+            for relay in added {
+                relay_pool.dispatch(NostrRelayPoolAction::RemoveRelay(relay.clone()));
+                yew::platform::spawn_local(async move {
+                    if relay.delete_from_store().await.is_err() {
+                        web_sys::console::log_1(
+                            &format!("Failed to delete relay: {}", relay.url).into(),
+                        );
+                    }
+                });
+            }
+        }
+    });
+}
@@ -36,7 +36,7 @@ pub fn user_profile_card() -> Html {
                     <img
                         src={metadata.as_ref().and_then(|p| p.picture.clone())
                             .unwrap_or_else(|| "/public/assets/img/default-avatar.png".to_string())}
-                        alt="Profile"
+                        alt={ language_ctx.t("profile_title") }
                         class="w-full h-full object-cover"
                     />
                 </div>
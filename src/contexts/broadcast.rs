@@ -0,0 +1,167 @@
+use std::rc::Rc;
+
+use crate::models::{BroadcastContent, Naddr, BROADCAST_KIND};
+use yew::prelude::*;
+
+/// Shortest time between two broadcast notes, in milliseconds. Moves played
+/// faster are sent together once it's over.
+const BROADCAST_INTERVAL_MS: u32 = 5000;
+
+/// Live broadcast of the game being annotated, while it's on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BroadcastState {
+    /// `d` tag of the broadcast note and its author.
+    broadcast: Option<(String, String)>,
+    /// Write relays of the broadcaster, where spectators find its notes.
+    relays: Vec<String>,
+}
+
+impl BroadcastState {
+    #[must_use]
+    pub const fn is_live(&self) -> bool {
+        self.broadcast.is_some()
+    }
+    /// Address spectators follow the broadcast with.
+    #[must_use]
+    pub fn naddr(&self) -> Option<Naddr> {
+        self.broadcast.as_ref().map(|(identifier, pubkey)| Naddr {
+            identifier: identifier.clone(),
+            pubkey: pubkey.clone(),
+            kind: BROADCAST_KIND,
+            relays: self.relays.clone(),
+        })
+    }
+}
+
+pub enum BroadcastAction {
+    /// Starts broadcasting as `pubkey` to `relays`, under a new `d` tag.
+    Start {
+        pubkey: String,
+        relays: Vec<String>,
+    },
+    Stop,
+}
+
+impl Reducible for BroadcastState {
+    type Action = BroadcastAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            BroadcastAction::Start { pubkey, relays } => {
+                let identifier = format!("rooky-{}", chrono::Utc::now().timestamp_millis());
+                Rc::new(Self {
+                    broadcast: Some((identifier, pubkey)),
+                    relays,
+                })
+            }
+            BroadcastAction::Stop => Rc::new(Self::default()),
+        }
+    }
+}
+
+pub type BroadcastStore = UseReducerHandle<BroadcastState>;
+
+/// Notes sent so far, to throttle the next ones.
+#[derive(Default)]
+struct Throttle {
+    last_sent_ms: f64,
+    /// Addressable notes replace each other by `created_at`, so it never repeats.
+    last_created_at: i64,
+}
+
+#[derive(Clone, Debug, Properties, PartialEq)]
+pub struct BroadcastProviderProps {
+    pub children: Children,
+}
+
+/// Publishes the live game while a broadcast is on: after every move of the
+/// main line at most once per `BROADCAST_INTERVAL_MS`, and right away
+/// when the result is set.
+///
+/// Sits above the router, so the final result still goes out after the
+/// game moves on to the review page.
+#[function_component(BroadcastProvider)]
+pub fn broadcast_provider(props: &BroadcastProviderProps) -> Html {
+    let ctx = use_reducer(BroadcastState::default);
+    let game_ctx = crate::live_game::use_annotated_game();
+    let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
+        .expect("Relay context not found");
+    let signer = crate::signer::use_signer().signer();
+    let throttle = use_mut_ref(Throttle::default);
+
+    {
+        let content = BroadcastContent {
+            pgn: game_ctx.to_pgn(),
+            fen: shakmaty::fen::Fen::from_position(
+                game_ctx.last_game_position(),
+                shakmaty::EnPassantMode::Legal,
+            )
+            .to_string(),
+        };
+        use_effect_with(
+            (
+                ctx.broadcast.clone(),
+                game_ctx.game_tree().mainline_end(),
+                game_ctx.has_outcome(),
+            ),
+            move |(broadcast, _, has_outcome)| {
+                let publish =
+                    broadcast
+                        .clone()
+                        .zip(signer)
+                        .map(|((identifier, pubkey), signer)| {
+                            let throttle = throttle.clone();
+                            move || {
+                                let mut throttle = throttle.borrow_mut();
+                                let created_at = chrono::Utc::now()
+                                    .timestamp()
+                                    .max(throttle.last_created_at + 1);
+                                let mut note = nostr_minions::nostro2::NostrNote {
+                                    pubkey,
+                                    created_at,
+                                    kind: BROADCAST_KIND,
+                                    content: content.to_string(),
+                                    ..Default::default()
+                                };
+                                note.tags.add_parameter_tag(&identifier);
+                                throttle.last_sent_ms = web_sys::js_sys::Date::now();
+                                throttle.last_created_at = created_at;
+                                // Remote signers answer later, the throttle already counts this note
+                                yew::platform::spawn_local(async move {
+                                    if let Err(e) = signer.sign_note(&mut note).await {
+                                        web_sys::console::error_1(
+                                            &format!("Failed to sign broadcast: {e}").into(),
+                                        );
+                                        return;
+                                    }
+                                    relay_ctx.send(note);
+                                });
+                            }
+                        });
+                let wait = f64::from(BROADCAST_INTERVAL_MS)
+                    - (web_sys::js_sys::Date::now() - throttle.borrow().last_sent_ms);
+                // A newer move replaces the one waiting, sent when the interval is over
+                let pending = publish.and_then(|publish| {
+                    if *has_outcome || wait <= 0.0 {
+                        publish();
+                        None
+                    } else {
+                        Some(gloo::timers::callback::Timeout::new(wait as u32, publish))
+                    }
+                });
+                move || drop(pending)
+            },
+        );
+    }
+
+    html! {
+        <ContextProvider<BroadcastStore> context={ctx}>
+            {props.children.clone()}
+        </ContextProvider<BroadcastStore>>
+    }
+}
+
+#[hook]
+pub fn use_broadcast() -> BroadcastStore {
+    use_context::<BroadcastStore>().expect("BroadcastStore context")
+}
@@ -24,6 +24,19 @@ pub enum Language {
     Spanish,
     Portuguese,
 }
+impl Language {
+    pub const ALL: [Self; 3] = [Self::English, Self::Spanish, Self::Portuguese];
+
+    /// Name of the language written in that language.
+    #[must_use]
+    pub const fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Spanish => "Español",
+            Self::Portuguese => "Português",
+        }
+    }
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, Default)]
 pub enum BoardPlayingSide {
     #[default]
@@ -55,6 +68,9 @@ pub struct AnnotatorConfigurationEntry {
     pub language: Language,
     pub experience_level: ExperienceLevel,
     pub playing_as: BoardPlayingSide,
+    /// Show piece figurines instead of letters in moves.
+    #[serde(default)]
+    pub figurine_notation: bool,
 }
 impl Default for AnnotatorConfigurationEntry {
     fn default() -> Self {
@@ -63,6 +79,7 @@ impl Default for AnnotatorConfigurationEntry {
             language: Language::English,
             experience_level: ExperienceLevel::Rookie,
             playing_as: BoardPlayingSide::White,
+            figurine_notation: false,
         }
     }
 }
@@ -98,6 +115,14 @@ pub struct AnnotatorConfig {
     pub language: Language,
     pub experience_level: ExperienceLevel,
     pub playing_as: BoardPlayingSide,
+    pub figurine_notation: bool,
+}
+
+impl AnnotatorConfig {
+    #[must_use]
+    pub const fn san_notation(&self) -> crate::models::SanNotation {
+        crate::models::SanNotation::new(self.language, self.figurine_notation)
+    }
 }
 
 pub enum AnnotatorConfigAction {
@@ -106,6 +131,7 @@ pub enum AnnotatorConfigAction {
     SetLanguage(Language),
     SetExperienceLevel(ExperienceLevel),
     SetPlayingAs(BoardPlayingSide),
+    SetFigurineNotation(bool),
 }
 
 impl Reducible for AnnotatorConfig {
@@ -118,6 +144,7 @@ impl Reducible for AnnotatorConfig {
                     language,
                     experience_level: self.experience_level,
                     playing_as: self.playing_as,
+                    figurine_notation: self.figurine_notation,
                     ..Default::default()
                 };
                 yew::platform::spawn_local(async move {
@@ -131,6 +158,7 @@ impl Reducible for AnnotatorConfig {
                     language,
                     experience_level: self.experience_level,
                     playing_as: self.playing_as,
+                    figurine_notation: self.figurine_notation,
                 })
             }
             AnnotatorConfigAction::SetExperienceLevel(experience_level) => {
@@ -138,6 +166,7 @@ impl Reducible for AnnotatorConfig {
                     language: self.language,
                     experience_level,
                     playing_as: self.playing_as,
+                    figurine_notation: self.figurine_notation,
                     ..Default::default()
                 };
                 yew::platform::spawn_local(async move {
@@ -150,6 +179,7 @@ impl Reducible for AnnotatorConfig {
                     language: self.language,
                     experience_level,
                     playing_as: self.playing_as,
+                    figurine_notation: self.figurine_notation,
                 })
             }
             AnnotatorConfigAction::LoadConfig(AnnotatorConfigurationEntry {
@@ -157,23 +187,27 @@ impl Reducible for AnnotatorConfig {
                 language,
                 experience_level,
                 playing_as,
+                figurine_notation,
             }) => std::rc::Rc::new(Self {
                 loaded: true,
                 language,
                 experience_level,
                 playing_as,
+                figurine_notation,
             }),
             AnnotatorConfigAction::Loaded => std::rc::Rc::new(Self {
                 loaded: true,
                 language: self.language,
                 experience_level: self.experience_level,
                 playing_as: self.playing_as,
+                figurine_notation: self.figurine_notation,
             }),
             AnnotatorConfigAction::SetPlayingAs(playing_as) => {
                 let new_entry = AnnotatorConfigurationEntry {
                     playing_as,
                     experience_level: self.experience_level,
                     language: self.language,
+                    figurine_notation: self.figurine_notation,
                     ..Default::default()
                 };
                 yew::platform::spawn_local(async move {
@@ -187,6 +221,29 @@ impl Reducible for AnnotatorConfig {
                     loaded: self.loaded,
                     language: self.language,
                     experience_level: self.experience_level,
+                    figurine_notation: self.figurine_notation,
+                })
+            }
+            AnnotatorConfigAction::SetFigurineNotation(figurine_notation) => {
+                let new_entry = AnnotatorConfigurationEntry {
+                    language: self.language,
+                    experience_level: self.experience_level,
+                    playing_as: self.playing_as,
+                    figurine_notation,
+                    ..Default::default()
+                };
+                yew::platform::spawn_local(async move {
+                    if let Err(e) = new_entry.save_to_store().await {
+                        web_sys::console::error_1(&format!("Error saving config: {:?}", e).into());
+                    }
+                });
+
+                std::rc::Rc::new(Self {
+                    figurine_notation,
+                    loaded: self.loaded,
+                    language: self.language,
+                    experience_level: self.experience_level,
+                    playing_as: self.playing_as,
                 })
             }
         }
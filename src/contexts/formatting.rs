@@ -0,0 +1,154 @@
+use super::language::{AppLocale, LanguageConfigs};
+use chrono::Datelike;
+
+const ENGLISH_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const SPANISH_MONTHS: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+const PORTUGUESE_MONTHS: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
+];
+
+/// Plural form of a message, as named in the translation bundles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralCategory {
+    One,
+    Other,
+}
+
+impl PluralCategory {
+    /// Form `count` takes in `locale`, following the CLDR rules.
+    #[must_use]
+    pub const fn of(locale: AppLocale, count: u64) -> Self {
+        match (locale, count) {
+            (AppLocale::English | AppLocale::Spanish, 1) => Self::One,
+            // Portuguese uses the singular for zero as well
+            (AppLocale::Portuguese, 0 | 1) => Self::One,
+            _ => Self::Other,
+        }
+    }
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::One => "one",
+            Self::Other => "other",
+        }
+    }
+}
+
+fn month_name(locale: AppLocale, month: u32) -> &'static str {
+    let months = match locale {
+        AppLocale::English => &ENGLISH_MONTHS,
+        AppLocale::Spanish => &SPANISH_MONTHS,
+        AppLocale::Portuguese => &PORTUGUESE_MONTHS,
+    };
+    months
+        .get(month.saturating_sub(1) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Long form of `date`, like "March 5, 2024" or "5 de marzo de 2024".
+#[must_use]
+pub fn format_date(locale: AppLocale, date: chrono::NaiveDate) -> String {
+    let month = month_name(locale, date.month());
+    match locale {
+        AppLocale::English => format!("{month} {}, {}", date.day(), date.year()),
+        AppLocale::Spanish | AppLocale::Portuguese => {
+            format!("{} de {month} de {}", date.day(), date.year())
+        }
+    }
+}
+
+/// Formats the value of a PGN `Date` header, where unknown parts are written
+/// as `??`. Returns `None` when not even the year is known.
+#[must_use]
+pub fn format_pgn_date(locale: AppLocale, date: &str) -> Option<String> {
+    let mut parts = date.split('.').map(|part| part.parse::<u32>().ok());
+    let year = i32::try_from(parts.next().flatten()?).ok()?;
+    let month = parts.next().flatten();
+    let day = parts.next().flatten();
+    match (month, day) {
+        (Some(month), Some(day)) => {
+            chrono::NaiveDate::from_ymd_opt(year, month, day).map(|date| format_date(locale, date))
+        }
+        (Some(month), None) if (1..=12).contains(&month) => {
+            let month = month_name(locale, month);
+            Some(match locale {
+                AppLocale::English => format!("{month} {year}"),
+                AppLocale::Spanish | AppLocale::Portuguese => format!("{month} de {year}"),
+            })
+        }
+        _ => Some(year.to_string()),
+    }
+}
+
+/// Result of a game in words, like "White Wins".
+#[must_use]
+pub fn outcome_text(language: &LanguageConfigs, outcome: shakmaty::Outcome) -> String {
+    match outcome {
+        shakmaty::Outcome::Draw => language.t("common_draw"),
+        shakmaty::Outcome::Decisive {
+            winner: shakmaty::Color::White,
+        } => language.t("common_white_wins"),
+        shakmaty::Outcome::Decisive {
+            winner: shakmaty::Color::Black,
+        } => language.t("common_black_wins"),
+    }
+}
+
+/// Reason a game ended in words, like "Resignation".
+#[must_use]
+pub fn termination_text(
+    language: &LanguageConfigs,
+    reason: crate::models::TerminationReason,
+) -> String {
+    use crate::models::TerminationReason;
+    match reason {
+        TerminationReason::Checkmate => language.t("termination_checkmate"),
+        TerminationReason::Stalemate => language.t("termination_stalemate"),
+        TerminationReason::Resignation => language.t("termination_resignation"),
+        TerminationReason::TimeForfeit => language.t("termination_time_forfeit"),
+        TerminationReason::Agreement => language.t("termination_agreement"),
+        TerminationReason::ThreefoldRepetition => language.t("termination_threefold_repetition"),
+        TerminationReason::FiftyMoveRule => language.t("termination_fifty_move_rule"),
+        TerminationReason::InsufficientMaterial => language.t("termination_insufficient_material"),
+        TerminationReason::Abandonment => language.t("termination_abandonment"),
+        TerminationReason::ArbiterDecision => language.t("termination_arbiter_decision"),
+    }
+}
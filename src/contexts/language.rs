@@ -7,6 +7,18 @@ use yew::prelude::*;
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AppLocale {
     English,
+    Spanish,
+    Portuguese,
+}
+
+impl From<crate::configs::Language> for AppLocale {
+    fn from(language: crate::configs::Language) -> Self {
+        match language {
+            crate::configs::Language::English => Self::English,
+            crate::configs::Language::Spanish => Self::Spanish,
+            crate::configs::Language::Portuguese => Self::Portuguese,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -27,6 +39,12 @@ impl LanguageConfigs {
     pub fn t(&self, key: &str) -> String {
         self.translations.get_translation(key)
     }
+
+    /// Translation of a message that depends on `count`, like "3 relays connected".
+    pub fn tn(&self, key: &str, count: u64) -> String {
+        let category = super::formatting::PluralCategory::of(self.locale, count);
+        self.translations.get_plural(key, category, count)
+    }
 }
 
 pub enum LanguageConfigsAction {
@@ -50,10 +68,21 @@ pub type LanguageConfigsStore = UseReducerHandle<LanguageConfigs>;
 
 #[function_component(LanguageConfigsProvider)]
 pub fn language_config_provider(props: &yew::html::ChildrenProps) -> Html {
+    let config_ctx = crate::configs::use_annotator_config();
+    let locale = AppLocale::from(config_ctx.language);
     let ctx = use_reducer(|| LanguageConfigs {
-        locale: AppLocale::English,
-        translations: TranslationData::default(),
+        locale,
+        translations: TranslationData::load_translation(locale),
     });
+    {
+        let ctx = ctx.clone();
+        use_effect_with(locale, move |locale| {
+            if ctx.current_locale() != *locale {
+                ctx.dispatch(LanguageConfigsAction::ChangeLocale(*locale));
+            }
+            || {}
+        });
+    }
 
     html! {
         <ContextProvider<LanguageConfigsStore> context={ctx}>
@@ -75,9 +104,24 @@ impl Default for TranslationData {
 }
 
 impl TranslationData {
+    /// Translations of `locale`, keys missing from its bundle keep their English text.
     pub fn load_translation(locale: AppLocale) -> Self {
+        let mut data: Self = serde_json::from_str(ENGLISH_TRANSLATIONS).unwrap();
+        let annotator: Self = serde_json::from_str(ANNOTATOR_ENGLISH_TRANSLATIONS).unwrap();
+        data.translations.extend(annotator.translations);
+        if let Some(bundle) = Self::locale_bundle(locale) {
+            let overlay: Self = serde_json::from_str(bundle).unwrap();
+            data.translations.extend(overlay.translations);
+        }
+        data
+    }
+
+    /// Bundle of a translated locale, English is the base of every locale.
+    const fn locale_bundle(locale: AppLocale) -> Option<&'static str> {
         match locale {
-            AppLocale::English => serde_json::from_str(ENGLISH_TRANSLATIONS).unwrap(),
+            AppLocale::English => None,
+            AppLocale::Spanish => Some(SPANISH_TRANSLATIONS),
+            AppLocale::Portuguese => Some(PORTUGUESE_TRANSLATIONS),
         }
     }
 
@@ -96,9 +140,31 @@ impl TranslationData {
         }
 
         // Return the key if no translation is found
+        #[cfg(debug_assertions)]
+        web_sys::console::warn_1(&format!("Missing translation for key: {key}").into());
         key.to_string()
     }
 
+    /// Picks the `one` or `other` form of a plural entry and fills in `{count}`.
+    /// Entries written as a plain string are used for every count.
+    pub fn get_plural(
+        &self,
+        key: &str,
+        category: super::formatting::PluralCategory,
+        count: u64,
+    ) -> String {
+        let text = match self.translations.get(key) {
+            Some(Value::Object(forms)) => forms
+                .get(category.key())
+                .or_else(|| forms.get("other"))
+                .map(|value| self.extract_string(value)),
+            Some(value) => Some(self.extract_string(value)),
+            None => None,
+        }
+        .unwrap_or_else(|| self.get_translation(key));
+        text.replace("{count}", &count.to_string())
+    }
+
     fn extract_string(&self, value: &Value) -> String {
         if let Some(s) = value.as_str() {
             s.to_string()
@@ -110,9 +176,69 @@ impl TranslationData {
 
 // Use the flattened JSON structure for translations
 static ENGLISH_TRANSLATIONS: &str = include_str!("../../../static_resources/language/en.json");
+// Texts of the annotator that the shared English file doesn't have
+static ANNOTATOR_ENGLISH_TRANSLATIONS: &str = include_str!("translations/en.json");
+static SPANISH_TRANSLATIONS: &str = include_str!("translations/es.json");
+static PORTUGUESE_TRANSLATIONS: &str = include_str!("translations/pt.json");
 
 // Helper function to use the language context
 #[hook]
 pub fn use_language_ctx() -> LanguageConfigsStore {
     use_context::<LanguageConfigsStore>().expect("LanguageConfigsStore context not set")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keys passed to `t("...")` or `tn("...")` anywhere in the crate sources.
+    fn keys_used_in_source() -> Vec<String> {
+        let mut keys = Vec::new();
+        let mut dirs = vec![std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src"
+        ))];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    let source = std::fs::read_to_string(&path).unwrap();
+                    for call in [".t(\"", ".tn(\""] {
+                        for (index, pattern) in source.match_indices(call) {
+                            let rest = &source[index + pattern.len()..];
+                            if let Some(end) = rest.find('"') {
+                                keys.push(rest[..end].to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    #[test]
+    fn every_used_key_is_in_every_locale() {
+        let keys = keys_used_in_source();
+        assert!(!keys.is_empty());
+        for locale in [
+            AppLocale::English,
+            AppLocale::Spanish,
+            AppLocale::Portuguese,
+        ] {
+            let bundle = TranslationData::locale_bundle(locale).map_or_else(
+                || TranslationData::load_translation(locale),
+                |bundle| serde_json::from_str(bundle).unwrap(),
+            );
+            let missing = keys
+                .iter()
+                .filter(|key| !bundle.translations.contains_key(*key))
+                .collect::<Vec<_>>();
+            assert!(missing.is_empty(), "{locale:?} is missing {missing:?}");
+        }
+    }
+}
@@ -1,13 +1,141 @@
 use std::rc::Rc;
 
+use crate::models::{
+    ChessClock, GameTree, GameVariant, MoveId, StoredTree, TerminationReason, TimeControl,
+};
+use nostr_minions::browser_api::IdbStoreManager;
 use shakmaty::Position;
 use yew::prelude::*;
 
+const LIVE_GAME_ID: &str = "live_game";
+/// Edits kept for undo, the oldest are forgotten first.
+const HISTORY_LIMIT: usize = 100;
+
+/// Unfinished game kept in IndexedDB so a refresh doesn't lose it.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LiveGameEntry {
+    id: String,
+    pub white: String,
+    pub black: String,
+    pub date: String,
+    pub event: String,
+    pub site: String,
+    pub round: String,
+    /// PGN result token, `*` while the game is in progress.
+    pub result: String,
+    #[serde(default)]
+    pub time_control: Option<TimeControl>,
+    /// Why the game ended, `None` while in progress or when not known.
+    #[serde(default)]
+    pub termination: Option<TerminationReason>,
+    pub tree: StoredTree,
+}
+impl LiveGameEntry {
+    /// Entry for a game read from PGN, with the cursor at the start.
+    #[must_use]
+    pub fn from_pgn_game(game: &crate::models::PgnGame) -> Self {
+        // Unknown tags are written as "?" in PGN
+        let known = |name: &str| {
+            game.header(name)
+                .filter(|value| *value != "?")
+                .unwrap_or_default()
+                .to_string()
+        };
+        let date = game
+            .header("Date")
+            .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y.%m.%d").ok())
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let event = known("Event");
+        Self {
+            id: LIVE_GAME_ID.to_string(),
+            white: known("White"),
+            black: known("Black"),
+            date,
+            event: if event.is_empty() {
+                "Casual".to_string()
+            } else {
+                event
+            },
+            site: known("Site"),
+            round: known("Round"),
+            result: game.header("Result").unwrap_or("*").to_string(),
+            time_control: game
+                .header("TimeControl")
+                .and_then(TimeControl::from_pgn_value),
+            termination: game
+                .header("Termination")
+                .and_then(TerminationReason::from_pgn_value),
+            tree: game.tree.to_stored(GameTree::ROOT),
+        }
+    }
+    /// Whether anything was typed in the game, an empty entry isn't worth resuming.
+    #[must_use]
+    pub fn has_content(&self) -> bool {
+        !self.tree.moves.is_empty()
+            || self.tree.comment.is_some()
+            || !self.white.is_empty()
+            || !self.black.is_empty()
+    }
+}
+impl TryFrom<web_sys::wasm_bindgen::JsValue> for LiveGameEntry {
+    type Error = web_sys::wasm_bindgen::JsValue;
+    fn try_from(value: web_sys::wasm_bindgen::JsValue) -> Result<Self, Self::Error> {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+}
+impl From<LiveGameEntry> for web_sys::wasm_bindgen::JsValue {
+    fn from(value: LiveGameEntry) -> Self {
+        serde_wasm_bindgen::to_value(&value).unwrap_or_default()
+    }
+}
+
+impl nostr_minions::browser_api::IdbStoreManager for LiveGameEntry {
+    fn config() -> nostr_minions::browser_api::IdbStoreConfig {
+        nostr_minions::browser_api::IdbStoreConfig {
+            db_name: "annotator_live_game_db",
+            store_name: "annotator_live_game_store",
+            db_version: 1,
+            document_key: "id",
+        }
+    }
+    fn key(&self) -> web_sys::wasm_bindgen::JsValue {
+        web_sys::wasm_bindgen::JsValue::from_str(&self.id)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AnnotatedGame {
     has_loaded: bool,
-    game_positions: Vec<shakmaty::Chess>,
+    game_tree: GameTree,
+    /// Move currently shown, the next played move follows it.
+    cursor: MoveId,
     pgn_game: rooky_core::RookyGame,
+    /// Unfinished game found on startup, waiting for the user to resume or discard it.
+    saved_game: Option<LiveGameEntry>,
+    /// Clock of an over-the-board game, `None` when the game isn't timed.
+    clock: Option<ChessClock>,
+    termination: Option<TerminationReason>,
+    /// Draw the last move made claimable, offered until the game goes on or ends.
+    draw_claim: Option<TerminationReason>,
+    /// Whether the last move played ended the game, to move on to the review.
+    ended_by_move: bool,
+    /// Game before each recorded edit, the latest last.
+    undo: Vec<GameSnapshot>,
+    /// Edits undone, replayed in reverse order by redo.
+    redo: Vec<GameSnapshot>,
+}
+
+/// Part of the game restored by undo and redo.
+///
+/// Only the time control of the clock is kept, time spent thinking can't be taken back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct GameSnapshot {
+    game_tree: GameTree,
+    cursor: MoveId,
+    pgn_game: rooky_core::RookyGame,
+    termination: Option<TerminationReason>,
+    time_control: Option<TimeControl>,
 }
 
 impl AnnotatedGame {
@@ -16,45 +144,369 @@ impl AnnotatedGame {
         self.has_loaded
     }
     #[must_use]
-    pub fn last_game_position(&self) -> shakmaty::Chess {
-        self.game_positions
-            .last()
-            .cloned()
-            .unwrap_or(shakmaty::Chess::default())
+    pub fn last_game_position(&self) -> shakmaty::variant::VariantPosition {
+        self.game_tree.position(self.game_tree.mainline_end())
+    }
+    #[must_use]
+    pub fn cursor_position(&self) -> shakmaty::variant::VariantPosition {
+        self.game_tree.position(self.cursor)
+    }
+    #[must_use]
+    pub fn variant(&self) -> GameVariant {
+        self.game_tree.variant()
+    }
+    #[must_use]
+    pub const fn saved_game(&self) -> Option<&LiveGameEntry> {
+        self.saved_game.as_ref()
     }
     #[must_use]
     pub fn pgn_game(&self) -> &rooky_core::RookyGame {
         &self.pgn_game
     }
     #[must_use]
+    pub const fn game_tree(&self) -> &GameTree {
+        &self.game_tree
+    }
+    #[must_use]
+    pub const fn cursor(&self) -> MoveId {
+        self.cursor
+    }
+    #[must_use]
+    pub const fn clock(&self) -> Option<&ChessClock> {
+        self.clock.as_ref()
+    }
+    #[must_use]
+    pub const fn termination(&self) -> Option<TerminationReason> {
+        self.termination
+    }
+    #[must_use]
+    pub const fn draw_claim(&self) -> Option<TerminationReason> {
+        self.draw_claim
+    }
+    /// Whether a result was recorded, by a move or by hand.
+    #[must_use]
+    pub fn has_outcome(&self) -> bool {
+        pgn_header_value(&self.pgn_game.to_pgn(), "Result").is_some_and(|result| result != "*")
+    }
+    #[must_use]
+    pub const fn ended_by_move(&self) -> bool {
+        self.ended_by_move
+    }
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+    #[must_use]
     pub fn color_turn(&self) -> shakmaty::Color {
-        self.game_positions
-            .last()
-            .map_or(shakmaty::Color::White, |pos| pos.turn())
+        self.cursor_position().turn()
     }
     #[must_use]
     pub fn legal_moves(&self) -> Vec<shakmaty::Move> {
-        self.game_positions
-            .last()
-            .cloned()
-            .unwrap_or_default()
-            .legal_moves()
-            .to_vec()
+        self.cursor_position().legal_moves().to_vec()
+    }
+    /// Whether playing `mv` at the cursor would diverge from moves already recorded,
+    /// so the user has to choose between overwriting the line or starting a variation.
+    #[must_use]
+    pub fn needs_insert_choice(&self, mv: &shakmaty::Move) -> bool {
+        !self.game_tree.children(self.cursor).is_empty()
+            && self.game_tree.find_child(self.cursor, mv).is_none()
     }
+    /// PGN of the game including every variation.
+    ///
+    /// Headers are taken from the `RookyGame` export, the movetext is written
+    /// from the move tree.
+    #[must_use]
+    pub fn to_pgn(&self) -> String {
+        let rooky_pgn = self.pgn_game.to_pgn();
+        let mut headers = rooky_pgn
+            .lines()
+            .filter(|line| line.starts_with('['))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let variant = self.game_tree.variant();
+        if variant != GameVariant::Standard {
+            headers.push(format!("[Variant \"{}\"]", variant.pgn_name()));
+        }
+        if let Some(clock) = &self.clock {
+            headers.push(format!(
+                "[TimeControl \"{}\"]",
+                clock.time_control().pgn_value()
+            ));
+        }
+        if let Some(termination) = self.termination {
+            headers.push(format!("[Termination \"{}\"]", termination.pgn_value()));
+        }
+        if let Some(fen) = self.game_tree.setup_fen() {
+            headers.push("[SetUp \"1\"]".to_string());
+            headers.push(format!("[FEN \"{fen}\"]"));
+        }
+        let headers = headers.join("\n");
+        let result = pgn_header_value(&rooky_pgn, "Result").unwrap_or_else(|| "*".to_string());
+        let movetext = self.game_tree.to_movetext();
+        if movetext.is_empty() {
+            format!("{headers}\n\n{result}\n")
+        } else {
+            format!("{headers}\n\n{movetext} {result}\n")
+        }
+    }
+    #[must_use]
+    pub fn to_entry(&self) -> LiveGameEntry {
+        let game = &self.pgn_game;
+        LiveGameEntry {
+            id: LIVE_GAME_ID.to_string(),
+            white: game.white.clone(),
+            black: game.black.clone(),
+            date: game.date.format("%Y-%m-%d").to_string(),
+            event: match &game.event {
+                rooky_core::pgn_standards::PgnEvent::Named(name) => name.clone(),
+                _ => "Casual".to_string(),
+            },
+            site: match &game.site {
+                rooky_core::pgn_standards::PgnSite::Named(name) => name.clone(),
+                _ => String::new(),
+            },
+            round: match &game.round {
+                rooky_core::pgn_standards::PgnRound::Named(name) => name.clone(),
+                _ => String::new(),
+            },
+            result: pgn_header_value(&game.to_pgn(), "Result").unwrap_or_else(|| "*".to_string()),
+            time_control: self.clock.as_ref().map(ChessClock::time_control),
+            termination: self.termination,
+            tree: self.game_tree.to_stored(self.cursor),
+        }
+    }
+    /// Rebuilds the game stored in `entry`, `None` if the stored moves can't be replayed.
+    fn restored_from(&self, entry: &LiveGameEntry) -> Option<Self> {
+        let (game_tree, cursor) = GameTree::from_stored(&entry.tree)?;
+        let mut pgn_game = rooky_core::RookyGame::default()
+            .add_white_name(entry.white.clone())
+            .add_black_name(entry.black.clone())
+            .add_event(entry.event.clone())
+            .add_site(entry.site.clone())
+            .add_round(entry.round.clone());
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d") {
+            pgn_game = pgn_game.add_date(date);
+        }
+        if let Ok(outcome) = entry.result.parse::<shakmaty::Outcome>() {
+            pgn_game = pgn_game.add_result(outcome);
+        }
+        // The clock is paused with the last times recorded on the main line
+        let end = game_tree.mainline_end();
+        let clock = entry.time_control.map(|time_control| {
+            ChessClock::resumed(
+                time_control,
+                game_tree.last_clock(end, shakmaty::Color::White),
+                game_tree.last_clock(end, shakmaty::Color::Black),
+            )
+        });
+        Some(
+            Self {
+                pgn_game,
+                clock,
+                termination: entry.termination,
+                ..self.clone()
+            }
+            .with_tree(game_tree, cursor),
+        )
+    }
+    /// New game from `start`, keeping the players and event details.
+    fn restarted_from(&self, start: shakmaty::variant::VariantPosition) -> Option<Self> {
+        let mut entry = self.to_entry();
+        entry.result = "*".to_string();
+        entry.termination = None;
+        entry.tree = GameTree::new(start).to_stored(GameTree::ROOT);
+        self.restored_from(&entry)
+    }
+    fn play_at_cursor(&self, mv: &shakmaty::Move, overwrite: bool) -> Option<Self> {
+        let mut game_tree = self.game_tree.clone();
+        let cursor = if overwrite {
+            game_tree.overwrite(self.cursor, mv)?
+        } else {
+            game_tree.play(self.cursor, mv)?
+        };
+        // A running clock switches sides and records the time left to the mover,
+        // only for moves extending the main line: variations are analysis
+        let extends_mainline = self.cursor == self.game_tree.mainline_end();
+        let mut clock = self.clock.clone();
+        if let Some((pressed, left)) = self
+            .clock
+            .as_ref()
+            .filter(|_| extends_mainline)
+            .and_then(|clock| clock.pressed(self.color_turn(), crate::models::now_millis()))
+        {
+            game_tree.set_clock(cursor, Some(left));
+            clock = Some(pressed);
+        }
+        let mut new_game = Self {
+            clock,
+            ..self.with_tree(game_tree, cursor)
+        };
+        if !new_game.game_tree.is_mainline(cursor) {
+            return Some(new_game);
+        }
+        let new_position = new_game.game_tree.position(cursor);
+        if let Some(outcome) = new_position.outcome() {
+            new_game.pgn_game = new_game.pgn_game.add_result(outcome);
+            new_game.termination = TerminationReason::of_position(&new_position);
+            new_game.ended_by_move = true;
+            new_game.clock = new_game
+                .clock
+                .map(|clock| clock.paused(crate::models::now_millis()));
+        } else {
+            // Repetitions and the fifty-move rule only end the game when claimed
+            new_game.draw_claim = new_game.game_tree.claimable_draw(cursor);
+        }
+        Some(new_game)
+    }
+    /// Runs a running clock for the side to move at the end of the main line,
+    /// once moves were taken back or restored.
+    fn with_clock_on_turn(mut self) -> Self {
+        let turn = self.last_game_position().turn();
+        if let Some(clock) = self
+            .clock
+            .as_ref()
+            .filter(|clock| clock.running_side().is_some_and(|side| side != turn))
+        {
+            self.clock = Some(clock.start(turn, crate::models::now_millis()));
+        }
+        self
+    }
+    /// Clock running for the side to move at the end of the main line, whatever
+    /// move the cursor shows.
+    fn clock_started(&self, now: u64) -> Option<ChessClock> {
+        let turn = self.last_game_position().turn();
+        self.clock.as_ref().map(|clock| clock.start(turn, now))
+    }
+    /// Clock with the same time control, for a game starting over.
+    fn fresh_clock(&self) -> Option<ChessClock> {
+        self.clock
+            .as_ref()
+            .map(|clock| ChessClock::new(clock.time_control()))
+    }
+    fn with_tree(&self, game_tree: GameTree, cursor: MoveId) -> Self {
+        let mut pgn_game = self.pgn_game.clone();
+        pgn_game.moves = game_tree.mainline_sans();
+        Self {
+            game_tree,
+            cursor,
+            pgn_game,
+            draw_claim: None,
+            ended_by_move: false,
+            ..self.clone()
+        }
+    }
+    fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            game_tree: self.game_tree.clone(),
+            cursor: self.cursor,
+            pgn_game: self.pgn_game.clone(),
+            termination: self.termination,
+            time_control: self.clock.as_ref().map(ChessClock::time_control),
+        }
+    }
+    fn restored_snapshot(&self, snapshot: GameSnapshot) -> Self {
+        let clock = if snapshot.time_control == self.clock.as_ref().map(ChessClock::time_control) {
+            self.clock.clone()
+        } else {
+            snapshot.time_control.map(ChessClock::new)
+        };
+        Self {
+            clock,
+            game_tree: snapshot.game_tree,
+            cursor: snapshot.cursor,
+            pgn_game: snapshot.pgn_game,
+            termination: snapshot.termination,
+            draw_claim: None,
+            ended_by_move: false,
+            ..self.clone()
+        }
+        .with_clock_on_turn()
+    }
+}
+
+fn pgn_header_value(pgn: &str, name: &str) -> Option<String> {
+    pgn.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .strip_prefix(name)?
+            .trim()
+            .strip_prefix('"')?
+            .strip_suffix('"')
+            .map(ToString::to_string)
+    })
 }
+
 pub enum AnnotatedGameAction {
     FinishedLoading,
+    /// An unfinished game was found in storage, the user is asked to resume it.
+    FoundSavedGame(LiveGameEntry),
+    ResumeSavedGame,
+    DiscardSavedGame,
+    /// Replaces the live game with a stored one, to keep editing it.
+    LoadGame(LiveGameEntry),
+    /// Starts a new game from a custom position, keeping the game details.
+    SetStartPosition(shakmaty::variant::VariantPosition),
+    /// Starts the game over in another variant, keeping the game details.
+    SetVariant(GameVariant),
+    /// Times the game with a fresh clock, or stops timing it with `None`.
+    SetTimeControl(Option<TimeControl>),
+    /// Starts the time of the side to move at the cursor.
+    StartClock,
+    PauseClock,
     Reset,
+    /// Plays a move at the cursor, as a variation if the cursor already has a different continuation.
     PlayMove(shakmaty::Move),
+    /// Plays a move at the cursor, replacing the rest of the current line.
+    OverwriteMove(shakmaty::Move),
     TakeBack,
-    AddOutcome(shakmaty::Outcome),
-    AddWhiteName(String),
-    AddBlackName(String),
-    ChangeDate(chrono::NaiveDate),
-    UpdateEventDetails {
-        event: String,
-        site: String,
-        round: String,
+    GoToPly(MoveId),
+    StepForward,
+    StepBack,
+    GoToStart,
+    GoToEnd,
+    ReturnToMainLine,
+    PromoteVariation(MoveId),
+    DeleteVariation(MoveId),
+    /// Sets the comment after a move, [`GameTree::ROOT`] holds the comment before the game.
+    /// An empty comment removes it.
+    SetComment {
+        id: MoveId,
+        comment: String,
+    },
+    ToggleNag {
+        id: MoveId,
+        nag: u8,
+    },
+    /// Toggles several glyphs as a single edit, like accepting every engine suggestion.
+    ToggleNags(Vec<(MoveId, u8)>),
+    ClearNags(MoveId),
+    /// Sets the result, with the reason the game ended when known.
+    AddOutcome {
+        outcome: shakmaty::Outcome,
+        termination: Option<TerminationReason>,
+    },
+    /// Removes the result, to keep editing a game that was ended by mistake.
+    ClearOutcome,
+    /// Hides the offered draw claim, the game goes on.
+    DismissDrawClaim,
+    /// Reverts the last recorded edit.
+    Undo,
+    /// Replays the last undone edit.
+    Redo,
+    /// Saves the game details form as a single edit, fields left `None` keep
+    /// their value. Another variant starts the game over, like `SetVariant`.
+    UpdateDetails {
+        white: Option<String>,
+        black: Option<String>,
+        date: Option<chrono::NaiveDate>,
+        /// Event, site and round.
+        event: Option<(String, String, String)>,
+        variant: Option<GameVariant>,
     },
 }
 
@@ -62,88 +514,318 @@ impl Reducible for AnnotatedGame {
     type Action = AnnotatedGameAction;
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            action if action.is_recorded() => {
+                let before = self.snapshot();
+                let next = self.apply(action);
+                if next.snapshot() == before {
+                    return next;
+                }
+                let mut undo = next.undo.clone();
+                undo.push(before);
+                if undo.len() > HISTORY_LIMIT {
+                    undo.remove(0);
+                }
+                Rc::new(Self {
+                    undo,
+                    redo: vec![],
+                    ..(*next).clone()
+                })
+            }
+            action => self.apply(action),
+        }
+    }
+}
+
+impl AnnotatedGameAction {
+    /// Whether the action edits the game and can be undone.
+    /// Moving around the game, loading it and running the clock aren't recorded.
+    const fn is_recorded(&self) -> bool {
+        !matches!(
+            self,
+            Self::FinishedLoading
+                | Self::FoundSavedGame(_)
+                | Self::ResumeSavedGame
+                | Self::DiscardSavedGame
+                | Self::StartClock
+                | Self::PauseClock
+                | Self::GoToPly(_)
+                | Self::StepForward
+                | Self::StepBack
+                | Self::GoToStart
+                | Self::GoToEnd
+                | Self::ReturnToMainLine
+                | Self::DismissDrawClaim
+                | Self::Undo
+                | Self::Redo
+        )
+    }
+}
+
+impl AnnotatedGame {
+    fn apply(self: Rc<Self>, action: AnnotatedGameAction) -> Rc<Self> {
         match action {
             AnnotatedGameAction::FinishedLoading => Rc::new(Self {
                 has_loaded: true,
                 ..(*self).clone()
             }),
-            AnnotatedGameAction::Reset => Rc::new(Self {
-                game_positions: vec![shakmaty::Chess::default()],
-                pgn_game: rooky_core::RookyGame::default(),
+            AnnotatedGameAction::FoundSavedGame(entry) => Rc::new(Self {
+                saved_game: Some(entry),
                 ..(*self).clone()
             }),
-            AnnotatedGameAction::AddOutcome(outcome) => {
-                let mut pgn_game = self.pgn_game.clone();
-                pgn_game = pgn_game.add_result(outcome);
+            AnnotatedGameAction::ResumeSavedGame => {
+                let Some(entry) = self.saved_game.clone() else {
+                    return self;
+                };
+                let resumed = self.restored_from(&entry).unwrap_or_else(|| {
+                    web_sys::console::error_1(&"Saved game could not be replayed".into());
+                    (*self).clone()
+                });
                 Rc::new(Self {
-                    pgn_game,
-                    ..(*self).clone()
+                    has_loaded: true,
+                    saved_game: None,
+                    ..resumed
                 })
             }
-            AnnotatedGameAction::TakeBack => {
-                let mut game_positions = self.game_positions.clone();
-                let _ = game_positions.pop();
-                let mut pgn_game = self.pgn_game.clone();
-                let _ = pgn_game.take_back();
+            // A game loaded or set up by the user replaces the one waiting to be resumed
+            AnnotatedGameAction::LoadGame(entry) => {
+                let Some(loaded) = self.restored_from(&entry) else {
+                    web_sys::console::error_1(&"Game could not be loaded".into());
+                    return self;
+                };
                 Rc::new(Self {
-                    game_positions,
-                    pgn_game,
-                    ..(*self).clone()
+                    has_loaded: true,
+                    saved_game: None,
+                    ..loaded
                 })
             }
-            AnnotatedGameAction::PlayMove(mv) => {
-                let game_position = self.game_positions.last().cloned().unwrap_or_default();
-                let Ok(new_position) = game_position.clone().play(&mv) else {
-                    return Rc::new(Self { ..(*self).clone() });
+            AnnotatedGameAction::SetStartPosition(start) => {
+                let Some(started) = self.restarted_from(start) else {
+                    return self;
                 };
-                let mut pgn_game = self
-                    .pgn_game
-                    .clone()
-                    .new_move(shakmaty::san::SanPlus::from_move(game_position, &mv));
-                if new_position.is_checkmate() || new_position.is_stalemate() {
-                    if let Some(outcome) = new_position.outcome() {
-                        pgn_game = pgn_game.add_result(outcome);
-                    };
-                }
-                let mut game_positions = self.game_positions.clone();
-                game_positions.push(new_position);
                 Rc::new(Self {
-                    game_positions,
-                    pgn_game,
+                    has_loaded: true,
+                    saved_game: None,
+                    ..started
+                })
+            }
+            AnnotatedGameAction::SetTimeControl(time_control) => Rc::new(Self {
+                clock: time_control.map(ChessClock::new),
+                ..(*self).clone()
+            }),
+            AnnotatedGameAction::StartClock => {
+                let Some(clock) = self.clock_started(crate::models::now_millis()) else {
+                    return self;
+                };
+                Rc::new(Self {
+                    clock: Some(clock),
                     ..(*self).clone()
                 })
             }
-            AnnotatedGameAction::AddWhiteName(name) => {
-                let mut pgn_game = self.pgn_game.clone();
-                pgn_game = pgn_game.add_white_name(name);
+            AnnotatedGameAction::PauseClock => {
+                let Some(clock) = &self.clock else {
+                    return self;
+                };
+                let clock = clock.paused(crate::models::now_millis());
                 Rc::new(Self {
-                    pgn_game,
+                    clock: Some(clock),
                     ..(*self).clone()
                 })
             }
-            AnnotatedGameAction::AddBlackName(name) => {
+            AnnotatedGameAction::SetVariant(variant) => self
+                .restarted_from(variant.new_game_position())
+                .map_or(self, Rc::new),
+            AnnotatedGameAction::DiscardSavedGame => Rc::new(Self {
+                has_loaded: true,
+                saved_game: None,
+                ..(*self).clone()
+            }),
+            AnnotatedGameAction::Reset => Rc::new(Self {
+                game_tree: GameTree::new(self.variant().new_game_position()),
+                cursor: GameTree::ROOT,
+                pgn_game: rooky_core::RookyGame::default()
+                    .add_date(chrono::Local::now().date_naive()),
+                clock: self.fresh_clock(),
+                termination: None,
+                draw_claim: None,
+                ended_by_move: false,
+                ..(*self).clone()
+            }),
+            AnnotatedGameAction::AddOutcome {
+                outcome,
+                termination,
+            } => {
                 let mut pgn_game = self.pgn_game.clone();
-                pgn_game = pgn_game.add_black_name(name);
+                pgn_game = pgn_game.add_result(outcome);
                 Rc::new(Self {
                     pgn_game,
+                    termination,
+                    draw_claim: None,
                     ..(*self).clone()
                 })
             }
-            AnnotatedGameAction::ChangeDate(date) => {
-                let mut pgn_game = self.pgn_game.clone();
-                pgn_game = pgn_game.add_date(date);
+            AnnotatedGameAction::ClearOutcome => {
+                let mut entry = self.to_entry();
+                entry.result = "*".to_string();
+                entry.termination = None;
+                let Some(cleared) = self.restored_from(&entry) else {
+                    return self;
+                };
+                // The clock stays as it was, restoring would stop it
                 Rc::new(Self {
-                    pgn_game,
+                    clock: self.clock.clone(),
+                    ..cleared
+                })
+            }
+            AnnotatedGameAction::Undo => {
+                let mut undo = self.undo.clone();
+                let Some(snapshot) = undo.pop() else {
+                    return self;
+                };
+                let mut redo = self.redo.clone();
+                redo.push(self.snapshot());
+                Rc::new(Self {
+                    undo,
+                    redo,
+                    ..self.restored_snapshot(snapshot)
+                })
+            }
+            AnnotatedGameAction::Redo => {
+                let mut redo = self.redo.clone();
+                let Some(snapshot) = redo.pop() else {
+                    return self;
+                };
+                let mut undo = self.undo.clone();
+                undo.push(self.snapshot());
+                Rc::new(Self {
+                    undo,
+                    redo,
+                    ..self.restored_snapshot(snapshot)
+                })
+            }
+            AnnotatedGameAction::DismissDrawClaim => Rc::new(Self {
+                draw_claim: None,
+                ..(*self).clone()
+            }),
+            AnnotatedGameAction::TakeBack => {
+                // Only the last move of a line is destroyed, in the middle of a line this just steps back
+                if !self.game_tree.children(self.cursor).is_empty() {
+                    return self.apply(AnnotatedGameAction::StepBack);
+                }
+                let mut game_tree = self.game_tree.clone();
+                let Some(cursor) = game_tree.delete_variation(self.cursor) else {
+                    return self;
+                };
+                Rc::new(self.with_tree(game_tree, cursor).with_clock_on_turn())
+            }
+            AnnotatedGameAction::PlayMove(mv) => {
+                self.play_at_cursor(&mv, false).map_or(self, Rc::new)
+            }
+            AnnotatedGameAction::OverwriteMove(mv) => {
+                self.play_at_cursor(&mv, true).map_or(self, Rc::new)
+            }
+            AnnotatedGameAction::GoToPly(id) => {
+                if self.game_tree.get(id).is_none() {
+                    return self;
+                }
+                Rc::new(Self {
+                    cursor: id,
                     ..(*self).clone()
                 })
             }
-            AnnotatedGameAction::UpdateEventDetails { event, site, round } => {
+            AnnotatedGameAction::StepForward => {
+                let Some(next) = self.game_tree.children(self.cursor).first().copied() else {
+                    return self;
+                };
+                self.apply(AnnotatedGameAction::GoToPly(next))
+            }
+            AnnotatedGameAction::StepBack => {
+                let Some(previous) = self.game_tree.parent(self.cursor) else {
+                    return self;
+                };
+                self.apply(AnnotatedGameAction::GoToPly(previous))
+            }
+            AnnotatedGameAction::GoToStart => {
+                self.apply(AnnotatedGameAction::GoToPly(GameTree::ROOT))
+            }
+            AnnotatedGameAction::GoToEnd => {
+                let end = self.game_tree.line_end(self.cursor);
+                self.apply(AnnotatedGameAction::GoToPly(end))
+            }
+            AnnotatedGameAction::ReturnToMainLine => {
+                let end = self.game_tree.mainline_end();
+                self.apply(AnnotatedGameAction::GoToPly(end))
+            }
+            AnnotatedGameAction::PromoteVariation(id) => {
+                let mut game_tree = self.game_tree.clone();
+                game_tree.promote_variation(id);
+                Rc::new(self.with_tree(game_tree, self.cursor))
+            }
+            AnnotatedGameAction::DeleteVariation(id) => {
+                let mut game_tree = self.game_tree.clone();
+                let Some(parent) = game_tree.delete_variation(id) else {
+                    return self;
+                };
+                // The cursor goes back to the branching point if it was inside the deleted line
+                let cursor = if self.game_tree.path_to(self.cursor).contains(&id) {
+                    parent
+                } else {
+                    self.cursor
+                };
+                Rc::new(self.with_tree(game_tree, cursor))
+            }
+            AnnotatedGameAction::SetComment { id, comment } => {
+                let mut game_tree = self.game_tree.clone();
+                game_tree.set_comment(id, &comment);
+                Rc::new(self.with_tree(game_tree, self.cursor))
+            }
+            AnnotatedGameAction::ToggleNag { id, nag } => {
+                let mut game_tree = self.game_tree.clone();
+                game_tree.toggle_nag(id, nag);
+                Rc::new(self.with_tree(game_tree, self.cursor))
+            }
+            AnnotatedGameAction::ToggleNags(nags) => {
+                let mut game_tree = self.game_tree.clone();
+                for (id, nag) in nags {
+                    game_tree.toggle_nag(id, nag);
+                }
+                Rc::new(self.with_tree(game_tree, self.cursor))
+            }
+            AnnotatedGameAction::ClearNags(id) => {
+                let mut game_tree = self.game_tree.clone();
+                game_tree.clear_nags(id);
+                Rc::new(self.with_tree(game_tree, self.cursor))
+            }
+            AnnotatedGameAction::UpdateDetails {
+                white,
+                black,
+                date,
+                event,
+                variant,
+            } => {
                 let mut pgn_game = self.pgn_game.clone();
-                pgn_game = pgn_game.add_event(event).add_site(site).add_round(round);
-                Rc::new(Self {
+                if let Some(white) = white {
+                    pgn_game = pgn_game.add_white_name(white);
+                }
+                if let Some(black) = black {
+                    pgn_game = pgn_game.add_black_name(black);
+                }
+                if let Some(date) = date {
+                    pgn_game = pgn_game.add_date(date);
+                }
+                if let Some((event, site, round)) = event {
+                    pgn_game = pgn_game.add_event(event).add_site(site).add_round(round);
+                }
+                let updated = Rc::new(Self {
                     pgn_game,
                     ..(*self).clone()
-                })
+                });
+                match variant {
+                    Some(variant) if variant != updated.variant() => {
+                        updated.apply(AnnotatedGameAction::SetVariant(variant))
+                    }
+                    _ => updated,
+                }
             }
         }
     }
@@ -160,15 +842,57 @@ pub struct AnnotatedGameChildren {
 pub fn key_handler(props: &AnnotatedGameChildren) -> Html {
     let pgn_game = rooky_core::RookyGame::default().add_date(chrono::Local::now().date_naive());
     let ctx = use_reducer(|| AnnotatedGame {
-        has_loaded: true,
-        game_positions: vec![shakmaty::Chess::new()],
+        has_loaded: false,
+        game_tree: GameTree::default(),
+        cursor: GameTree::ROOT,
         pgn_game,
+        saved_game: None,
+        clock: None,
+        termination: None,
+        draw_claim: None,
+        ended_by_move: false,
+        undo: vec![],
+        redo: vec![],
+    });
+    {
+        use_memo((), |_| {
+            let ctx = ctx.clone();
+            yew::platform::spawn_local(async move {
+                let entry =
+                    LiveGameEntry::retrieve_from_store::<LiveGameEntry>(&LIVE_GAME_ID.into()).await;
+                match entry {
+                    Ok(entry) if entry.has_content() => {
+                        ctx.dispatch(AnnotatedGameAction::FoundSavedGame(entry));
+                    }
+                    Ok(_) => ctx.dispatch(AnnotatedGameAction::FinishedLoading),
+                    Err(e) => {
+                        web_sys::console::error_1(
+                            &format!("Error loading live game: {:?}", e).into(),
+                        );
+                        ctx.dispatch(AnnotatedGameAction::FinishedLoading);
+                    }
+                }
+            });
+        });
+    }
+    // Saving waits for the stored game to be loaded, or it would be overwritten by the empty one
+    use_effect_with(ctx.clone(), |game| {
+        if game.has_loaded {
+            let entry = game.to_entry();
+            yew::platform::spawn_local(async move {
+                if let Err(e) = entry.save_to_store().await {
+                    web_sys::console::error_1(&format!("Error saving live game: {:?}", e).into());
+                }
+            });
+        }
+        || {}
     });
 
+    // The result is set by the move itself, so undoing or clearing it
+    // brings the game back to editing without it being set again
     let navigator = yew_router::hooks::use_navigator().expect("Navigator not found");
-    use_effect_with(ctx.clone(), move |game| {
-        if let Some(outcome) = game.last_game_position().outcome() {
-            game.dispatch(crate::live_game::AnnotatedGameAction::AddOutcome(outcome));
+    use_effect_with(ctx.ended_by_move(), move |ended| {
+        if *ended {
             navigator.push(&crate::router::AnnotatorRoute::Review);
         }
         || {}
@@ -181,7 +905,91 @@ pub fn key_handler(props: &AnnotatedGameChildren) -> Html {
     }
 }
 
+#[derive(Clone, Debug, Properties, PartialEq)]
+pub struct StoredGameProviderProps {
+    pub entry: LiveGameEntry,
+    pub children: Children,
+}
+
+/// Provides a stored game in place of the live one, without saving it or
+/// touching the game being annotated.
+#[function_component(StoredGameProvider)]
+pub fn stored_game_provider(props: &StoredGameProviderProps) -> Html {
+    let entry = props.entry.clone();
+    let ctx = use_reducer(move || {
+        let empty = AnnotatedGame {
+            has_loaded: true,
+            game_tree: GameTree::default(),
+            cursor: GameTree::ROOT,
+            pgn_game: rooky_core::RookyGame::default(),
+            saved_game: None,
+            clock: None,
+            termination: None,
+            draw_claim: None,
+            ended_by_move: false,
+            undo: vec![],
+            redo: vec![],
+        };
+        empty.restored_from(&entry).unwrap_or(empty)
+    });
+
+    html! {
+        <ContextProvider<AnnotatedGameStore> context={ctx}>
+            {props.children.clone()}
+        </ContextProvider<AnnotatedGameStore>>
+    }
+}
+
 #[hook]
 pub fn use_annotated_game() -> AnnotatedGameStore {
     use_context::<AnnotatedGameStore>().expect("AnnotatedGameStore context")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Game with the moves of `sans` on the main line and an untouched clock.
+    fn timed_game(sans: &[&str]) -> AnnotatedGame {
+        let mut game_tree = GameTree::default();
+        let mut cursor = GameTree::ROOT;
+        for san in sans {
+            let mv = san
+                .parse::<shakmaty::san::San>()
+                .unwrap()
+                .to_move(&game_tree.position(cursor))
+                .unwrap();
+            cursor = game_tree.play(cursor, &mv).unwrap();
+        }
+        AnnotatedGame {
+            has_loaded: true,
+            game_tree,
+            cursor,
+            pgn_game: rooky_core::RookyGame::default(),
+            saved_game: None,
+            clock: Some(ChessClock::new(TimeControl::new(300, 0, 0))),
+            termination: None,
+            draw_claim: None,
+            ended_by_move: false,
+            undo: vec![],
+            redo: vec![],
+        }
+    }
+
+    #[test]
+    fn clock_starts_for_the_side_to_move_at_the_end_of_the_game() {
+        let game = timed_game(&["e4", "e5"]);
+        let first_move = game.game_tree.mainline()[0];
+        let looking_back = AnnotatedGame {
+            cursor: first_move,
+            ..game
+        };
+        assert_eq!(looking_back.color_turn(), shakmaty::Color::Black);
+        let clock = looking_back.clock_started(0).unwrap();
+        assert_eq!(clock.running_side(), Some(shakmaty::Color::White));
+        assert_eq!(
+            timed_game(&["e4"]).clock_started(0).unwrap().running_side(),
+            Some(shakmaty::Color::Black)
+        );
+    }
+}
@@ -1,4 +1,7 @@
+pub mod broadcast;
 pub mod configs;
+pub mod formatting;
 pub mod language;
 pub mod live_game;
+pub mod signer;
 pub mod user_metadata;
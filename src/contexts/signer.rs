@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::models::{LoginMethod, LoginMethodEntry, RemoteSigner, Signer, NIP46_KIND};
+use nostr_minions::browser_api::IdbStoreManager;
+use nostr_minions::key_manager::{NostrIdStore, UserIdentity};
+use nostr_minions::nostro2::NostrNote;
+use yew::prelude::*;
+
+/// Who the app signs as, once the login is known.
+#[derive(Clone, Default, PartialEq)]
+pub struct SignerState {
+    signer: Option<Signer>,
+    /// Remote signer being connected to, or the one in use. Its responses
+    /// come with the other notes of the relay pool.
+    session: Option<RemoteSigner>,
+    loaded: bool,
+}
+
+impl SignerState {
+    #[must_use]
+    pub const fn loaded(&self) -> bool {
+        self.loaded
+    }
+    #[must_use]
+    pub fn signer(&self) -> Option<Signer> {
+        self.signer.clone()
+    }
+    #[must_use]
+    pub fn pubkey(&self) -> Option<String> {
+        self.signer
+            .as_ref()
+            .map(|signer| signer.pubkey().to_string())
+    }
+}
+
+pub enum SignerAction {
+    /// Login found when the app starts, if any.
+    Loaded(Option<Signer>),
+    /// Waits for a remote signer to accept the connection.
+    Connect(RemoteSigner),
+    SignIn(Signer),
+    SignOut,
+}
+
+impl Reducible for SignerState {
+    type Action = SignerAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let with_signer = |signer: Option<Signer>| {
+            let session = match &signer {
+                Some(Signer::Remote { session, .. }) => Some(session.clone()),
+                _ => None,
+            };
+            Rc::new(Self {
+                signer,
+                session,
+                loaded: true,
+            })
+        };
+        match action {
+            SignerAction::Loaded(signer) => with_signer(signer),
+            SignerAction::SignIn(signer) => with_signer(Some(signer)),
+            SignerAction::Connect(session) => Rc::new(Self {
+                session: Some(session),
+                ..(*self).clone()
+            }),
+            SignerAction::SignOut => with_signer(None),
+        }
+    }
+}
+
+pub type SignerStore = UseReducerHandle<SignerState>;
+
+/// Relay pool sender for the requests of a remote signer.
+#[must_use]
+pub fn relay_outbox(
+    relay_ctx: &nostr_minions::relay_pool::NostrRelayPoolStore,
+) -> Callback<NostrNote> {
+    let relay_ctx = relay_ctx.clone();
+    Callback::from(move |note: NostrNote| relay_ctx.send(note))
+}
+
+async fn local_signer(key_ctx: &NostrIdStore, identity: UserIdentity) -> Option<Signer> {
+    let keys = key_ctx.get_nostr_key().await?;
+    let pubkey = identity.get_pubkey().await?;
+    Some(Signer::Local {
+        identity,
+        keys,
+        pubkey,
+    })
+}
+
+async fn stored_signer(outbox: Callback<NostrNote>) -> Option<Signer> {
+    let entry =
+        LoginMethodEntry::retrieve_from_store::<LoginMethodEntry>(&LoginMethodEntry::ID.into())
+            .await
+            .ok()?;
+    match entry.method {
+        LoginMethod::Extension { pubkey } => Some(Signer::Extension { pubkey }),
+        LoginMethod::Remote(login) => Some(Signer::Remote {
+            session: RemoteSigner::restore(&login, outbox)?,
+            pubkey: login.user_pubkey,
+        }),
+    }
+}
+
+#[derive(Clone, Debug, Properties, PartialEq)]
+pub struct SignerProviderProps {
+    pub children: Children,
+}
+
+/// Signs for the rest of the app, with the keys of the key manager if there
+/// are any, or else with the extension or remote signer the user logged in with.
+#[function_component(SignerProvider)]
+pub fn signer_provider(props: &SignerProviderProps) -> Html {
+    let ctx = use_reducer(SignerState::default);
+    let key_ctx = nostr_minions::key_manager::use_nostr_id_ctx();
+    let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
+        .expect("Relay context not found");
+    // Remote signer responses already handed over, relays send them again with every new note
+    let handled = use_mut_ref(HashSet::<String>::new);
+
+    {
+        let ctx = ctx.clone();
+        let outbox = relay_outbox(&relay_ctx);
+        use_effect_with(
+            (key_ctx.loaded(), key_ctx.get_pubkey()),
+            move |(loaded, _)| {
+                if *loaded {
+                    let identity = key_ctx.get_identity().cloned();
+                    yew::platform::spawn_local(async move {
+                        let signer = match identity {
+                            Some(identity) => local_signer(&key_ctx, identity).await,
+                            None => stored_signer(outbox).await,
+                        };
+                        ctx.dispatch(SignerAction::Loaded(signer));
+                    });
+                }
+                || {}
+            },
+        );
+    }
+    // The signer answers on its own relays, which may not be in the pool yet
+    crate::components::use_session_relays(
+        ctx.session
+            .as_ref()
+            .map(|session| session.relays().to_vec())
+            .unwrap_or_default(),
+        true,
+    );
+    {
+        let relay_ctx = relay_ctx.clone();
+        use_effect_with(ctx.session.clone(), move |session| {
+            if let Some(session) = session {
+                let mut filter = nostr_minions::nostro2::NostrSubscription {
+                    kinds: Some(vec![NIP46_KIND]),
+                    ..Default::default()
+                };
+                filter.add_tag("#p", &session.client_pubkey());
+                relay_ctx.send(filter);
+            }
+            || {}
+        });
+    }
+    use_effect_with(
+        (relay_ctx.unique_notes.clone(), ctx.session.clone()),
+        move |(notes, session)| {
+            if let Some(session) = session {
+                for note in notes.iter().filter(|note| note.kind == NIP46_KIND) {
+                    if note
+                        .id
+                        .clone()
+                        .is_some_and(|id| handled.borrow_mut().insert(id))
+                    {
+                        session.receive(note);
+                    }
+                }
+            }
+            || {}
+        },
+    );
+
+    html! {
+        <ContextProvider<SignerStore> context={ctx}>
+            {props.children.clone()}
+        </ContextProvider<SignerStore>>
+    }
+}
+
+#[hook]
+pub fn use_signer() -> SignerStore {
+    use_context::<SignerStore>().expect("SignerStore context")
+}
@@ -82,40 +82,32 @@ pub struct UserMetadataProviderProps {
 pub fn user_metadata_provider(props: &UserMetadataProviderProps) -> Html {
     let user_metadata_store = use_reducer(|| UserMetadataStore::new(None));
 
-    let key_ctx = nostr_minions::key_manager::use_nostr_id_ctx();
+    let signer_ctx = crate::signer::use_signer();
     let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
         .expect("Relay context not found");
 
     // Just subscribe to metadata events once and update the store when new events arrive
     {
         let user_metadata_store = user_metadata_store.clone();
-        let key_ctx = key_ctx.clone();
         let relay_ctx = relay_ctx.clone();
 
-        // Subscribe to kind 0 metadata events matching our pubkey (once)
+        // Subscribe to kind 0 metadata events matching our pubkey
         {
             let relay_ctx = relay_ctx.clone();
-            let key_ctx = key_ctx.clone();
-
-            use_effect_with((), move |_| {
-                let key_ctx = key_ctx.clone();
-                if let Some(identity) = key_ctx.get_identity() {
-                    let identity = identity.clone();
-                    yew::platform::spawn_local(async move {
-                        if let Some(pubkey) = identity.get_pubkey().await {
-                            let metadata_filter = nostr_minions::nostro2::NostrSubscription {
-                                kinds: Some(vec![0]),        // Kind 0 for metadata
-                                authors: Some(vec![pubkey]), // Only look for our own events
-                                limit: Some(1),              // Only get the most recent
-                                ..Default::default()
-                            };
-
-                            // Subscribe to metadata events
-                            relay_ctx.send(metadata_filter);
-
-                            web_sys::console::log_1(&"Subscribed to metadata events".into());
-                        }
-                    });
+
+            use_effect_with(signer_ctx.pubkey(), move |pubkey| {
+                if let Some(pubkey) = pubkey {
+                    let metadata_filter = nostr_minions::nostro2::NostrSubscription {
+                        kinds: Some(vec![0]),                // Kind 0 for metadata
+                        authors: Some(vec![pubkey.clone()]), // Only look for our own events
+                        limit: Some(1),                      // Only get the most recent
+                        ..Default::default()
+                    };
+
+                    // Subscribe to metadata events
+                    relay_ctx.send(metadata_filter);
+
+                    web_sys::console::log_1(&"Subscribed to metadata events".into());
                 }
                 || {}
             });
@@ -125,45 +117,53 @@ pub fn user_metadata_provider(props: &UserMetadataProviderProps) -> Html {
         {
             let user_store = user_metadata_store.clone();
 
-            use_effect_with(relay_ctx.unique_notes.clone(), move |notes| {
-                // Process any new notes that might contain metadata
-                if let Some(note) = notes.last() {
-                    if note.kind == 0 {
-                        // Found a metadata note, trying to convert it
-                        if let Ok(metadata_idb) = UserMetadataIdb::try_from(note.clone()) {
-                            web_sys::console::log_1(
-                                &format!(
-                                    "Received metadata from relay: {:?}",
-                                    metadata_idb.metadata
-                                )
-                                .into(),
-                            );
-
-                            let toast_message = format!(
-                                "Received profile data for: {}",
-                                metadata_idb.metadata.name
-                            );
-                            let toast = ToastifyOptions::new_event_received(&toast_message);
-                            toast.show();
-
-                            // Log when metadata is received from relay
-                            web_sys::console::log_1(&"Metadata event received from relay".into());
-
-                            user_store
-                                .dispatch(UserMetadataAction::SetMetadata(Box::new(metadata_idb)));
+            // Other pages fetch profiles of other users too, only ours is taken
+            use_effect_with(
+                (relay_ctx.unique_notes.clone(), signer_ctx.pubkey()),
+                move |(notes, pubkey)| {
+                    // Process any new notes that might contain metadata
+                    if let Some(note) = notes.last() {
+                        if note.kind == 0
+                            && pubkey.as_ref().is_some_and(|pubkey| *pubkey == note.pubkey)
+                        {
+                            // Found a metadata note, trying to convert it
+                            if let Ok(metadata_idb) = UserMetadataIdb::try_from(note.clone()) {
+                                web_sys::console::log_1(
+                                    &format!(
+                                        "Received metadata from relay: {:?}",
+                                        metadata_idb.metadata
+                                    )
+                                    .into(),
+                                );
+
+                                let toast_message = format!(
+                                    "Received profile data for: {}",
+                                    metadata_idb.metadata.name
+                                );
+                                let toast = ToastifyOptions::new_event_received(&toast_message);
+                                toast.show();
+
+                                // Log when metadata is received from relay
+                                web_sys::console::log_1(
+                                    &"Metadata event received from relay".into(),
+                                );
+
+                                user_store.dispatch(UserMetadataAction::SetMetadata(Box::new(
+                                    metadata_idb,
+                                )));
+                            }
                         }
                     }
-                }
-                || {}
-            });
+                    || {}
+                },
+            );
         }
 
         // Set initial placeholder until we receive real data
         {
             let user_store = user_metadata_store.clone();
-            let key_ctx = key_ctx.clone();
 
-            use_effect_with(key_ctx.get_pubkey(), move |pubkey| {
+            use_effect_with(signer_ctx.pubkey(), move |pubkey| {
                 if pubkey.is_some() {
                     // Create a placeholder with default metadata
                     let default_metadata = NostrMetadata::default();
@@ -190,24 +190,23 @@ pub fn use_user_metadata_ctx() -> UseReducerHandle<UserMetadataStore> {
 /// Hook to get a callback for publishing user metadata to Nostr relays
 #[hook]
 pub fn use_publish_metadata() -> Callback<NostrMetadata, ()> {
-    let key_ctx = nostr_minions::key_manager::use_nostr_id_ctx();
+    let signer_ctx = crate::signer::use_signer();
     let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
         .expect("Relay context not found");
     let user_metadata_store = use_user_metadata_ctx();
 
     Callback::from(move |metadata: NostrMetadata| {
-        let key_ctx = key_ctx.clone();
         let relay_ctx = relay_ctx.clone();
         let user_metadata_store = user_metadata_store.clone();
 
-        // Skip if no identity is available
-        let Some(identity) = key_ctx.get_identity().cloned() else {
+        // Skip if nobody is logged in
+        let Some(signer) = signer_ctx.signer() else {
             return;
         };
 
         yew::platform::spawn_local(async move {
             // Creating a new UserMetadataIdb instance with the updated metadata
-            let metadata_idb = UserMetadataIdb::new(metadata.clone(), &identity).await;
+            let metadata_idb = UserMetadataIdb::new(metadata.clone(), &signer).await;
 
             // Get the signed note to publish
             let note = metadata_idb.signed_note();
@@ -55,20 +55,24 @@ fn app() -> Html {
         <yew_router::BrowserRouter>
         <nostr_minions::key_manager::NostrIdProvider>
             <nostr_minions::relay_pool::NostrRelayPoolProvider relays={(*relays).clone()}>
-                <PwaInstall />
+                <annotator::signer::SignerProvider>
                 <annotator::user_metadata::UserMetadataProvider>
-                <annotator::language::LanguageConfigsProvider>
                 <annotator::configs::AnnotatorConfigProvider>
+                <annotator::language::LanguageConfigsProvider>
+                <PwaInstall />
                 <div class={classes!("h-dvh", "w-dvw")}>
                 <LoginCheck>
                         <annotator::live_game::AnnotatedGameProvider>
+                        <annotator::broadcast::BroadcastProvider>
                             <annotator::AnnotatorRouter />
+                        </annotator::broadcast::BroadcastProvider>
                         </annotator::live_game::AnnotatedGameProvider>
                 </LoginCheck>
                 </div>
-                </annotator::configs::AnnotatorConfigProvider>
                 </annotator::language::LanguageConfigsProvider>
+                </annotator::configs::AnnotatorConfigProvider>
                 </annotator::user_metadata::UserMetadataProvider>
+                </annotator::signer::SignerProvider>
             </nostr_minions::relay_pool::NostrRelayPoolProvider>
         </nostr_minions::key_manager::NostrIdProvider>
         </yew_router::BrowserRouter>
@@ -77,11 +81,11 @@ fn app() -> Html {
 
 #[function_component(LoginCheck)]
 fn login_check(props: &yew::html::ChildrenProps) -> Html {
-    let key_ctx = nostr_minions::key_manager::use_nostr_id_ctx();
+    let signer_ctx = annotator::signer::use_signer();
     let config_ctx = annotator::configs::use_annotator_config();
 
-    let loaded = key_ctx.loaded() && config_ctx.loaded;
-    let nostr_id = key_ctx.get_pubkey();
+    let loaded = signer_ctx.loaded() && config_ctx.loaded;
+    let nostr_id = signer_ctx.pubkey();
 
     if !loaded {
         return html! {
@@ -181,12 +185,13 @@ pub fn nostr_actions() -> Html {
     let game_ctx =
         use_context::<annotator::live_game::AnnotatedGameStore>().expect("missing game context");
     let game = game_ctx.pgn_game();
+    let pgn = game_ctx.to_pgn();
 
     html! {
         <div class={classes!("flex", "flex-col", "gap-2", "justify-between", "items-center")}>
-            <annotator::ShareRookyGame game={game.clone()} />
-            <annotator::DirectMessageRookyGame game={game.clone()} />
-            <annotator::SaveTxtRookyGame game={game.clone()} />
+            <annotator::ShareRookyGame game={game.clone()} pgn={pgn.clone()} />
+            <annotator::DirectMessageRookyGame game={game.clone()} pgn={pgn.clone()} />
+            <annotator::SaveTxtRookyGame game={game.clone()} {pgn} />
         </div>
     }
 }
@@ -195,6 +200,7 @@ pub fn nostr_actions() -> Html {
 pub fn pwa_install() -> Html {
     let is_installable = use_state(|| None);
     let is_installed = use_state(|| false);
+    let language_ctx = annotator::language::use_language_ctx();
 
     let is_installable_handle = is_installable.clone();
     let is_installed_handle = is_installed.clone();
@@ -248,7 +254,7 @@ pub fn pwa_install() -> Html {
                         onclick={on_install}
                         >
                         <lucide_yew::Download class="w-5 h-5" />
-                        <span>{"Install App"}</span>
+                        <span>{ language_ctx.t("pwa_install_app") }</span>
                     </button>
                     <button
                       class="text-white p-2 rounded-full hover:bg-white hover:bg-opacity-20 focus:outline-none focus:ring-2 focus:ring-white focus:ring-opacity-50 transition-all duration-300"
@@ -0,0 +1,215 @@
+/// Centipawns a forced mate counts as, so mates compare with material evals.
+const MATE_SCORE: i32 = 10_000;
+
+/// Engine evaluation of a position, from white's point of view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Evaluation {
+    Centipawns(i32),
+    /// Moves to mate, negative when black mates.
+    Mate(i32),
+}
+
+impl Evaluation {
+    /// Eval in centipawns, mates counting as a large fixed score.
+    #[must_use]
+    pub const fn score(self) -> i32 {
+        match self {
+            Self::Centipawns(cp) => cp,
+            Self::Mate(moves) if moves >= 0 => MATE_SCORE - moves,
+            Self::Mate(moves) => -MATE_SCORE - moves,
+        }
+    }
+    /// Eval as usually shown, like `+0.35` or `#-3`.
+    #[must_use]
+    pub fn display(self) -> String {
+        match self {
+            Self::Centipawns(cp) => format!("{:+.2}", f64::from(cp) / 100.0),
+            Self::Mate(moves) => format!("#{moves}"),
+        }
+    }
+}
+
+/// What the engine reported about a position in its latest `info` line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EngineLine {
+    pub depth: u32,
+    pub evaluation: Evaluation,
+    /// Best line found, as UCI moves.
+    pub pv: Vec<String>,
+}
+
+/// Reads a UCI `info` line with a score, like
+/// `info depth 12 ... score cp 34 ... pv e2e4 e7e5`.
+///
+/// Engines score from the side to move, `turn` turns it to white's point of view.
+/// Lines without a score (`currmove` updates, strings) give `None`.
+#[must_use]
+pub fn parse_info_line(line: &str, turn: shakmaty::Color) -> Option<EngineLine> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next()? != "info" {
+        return None;
+    }
+    let mut depth = None;
+    let mut evaluation = None;
+    let mut pv = vec![];
+    while let Some(token) = tokens.next() {
+        match token {
+            "depth" => depth = tokens.next().and_then(|value| value.parse().ok()),
+            "score" => {
+                let kind = tokens.next()?;
+                let value = tokens.next()?.parse::<i32>().ok()?;
+                let sign = if turn == shakmaty::Color::White {
+                    1
+                } else {
+                    -1
+                };
+                evaluation = match kind {
+                    "cp" => Some(Evaluation::Centipawns(sign * value)),
+                    // The side to move is already mated, it has no sign of its own
+                    "mate" if value == 0 => Some(Evaluation::Centipawns(-sign * MATE_SCORE)),
+                    "mate" => Some(Evaluation::Mate(sign * value)),
+                    _ => None,
+                };
+            }
+            // Bounds are only estimates while the search fails high or low
+            "lowerbound" | "upperbound" => return None,
+            "pv" => {
+                pv = tokens.by_ref().map(ToString::to_string).collect();
+            }
+            _ => {}
+        }
+    }
+    Some(EngineLine {
+        depth: depth?,
+        evaluation: evaluation?,
+        pv,
+    })
+}
+
+/// Best line in SAN, as far as its moves are legal from `position`.
+#[must_use]
+pub fn pv_sans(
+    position: &shakmaty::variant::VariantPosition,
+    pv: &[String],
+) -> Vec<shakmaty::san::SanPlus> {
+    let mut position = position.clone();
+    let mut sans = vec![];
+    for uci in pv {
+        let Some(mv) = uci
+            .parse::<shakmaty::uci::UciMove>()
+            .ok()
+            .and_then(|uci| uci.to_move(&position).ok())
+        else {
+            break;
+        };
+        sans.push(shakmaty::san::SanPlus::from_move_and_play_unchecked(
+            &mut position,
+            &mv,
+        ));
+    }
+    sans
+}
+
+/// How much worse a move made the position, as judged by the eval drop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveJudgement {
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl MoveJudgement {
+    /// Judgement of a move by `mover` that took the eval from `before` to `after`,
+    /// `None` for moves losing less than half a pawn.
+    #[must_use]
+    pub fn of_move(before: Evaluation, after: Evaluation, mover: shakmaty::Color) -> Option<Self> {
+        let loss = match mover {
+            shakmaty::Color::White => before.score() - after.score(),
+            shakmaty::Color::Black => after.score() - before.score(),
+        };
+        match loss {
+            300.. => Some(Self::Blunder),
+            100..=299 => Some(Self::Mistake),
+            50..=99 => Some(Self::Inaccuracy),
+            _ => None,
+        }
+    }
+    /// Glyph suggested for the move: `?!`, `?` or `??`.
+    #[must_use]
+    pub const fn nag(self) -> u8 {
+        match self {
+            Self::Inaccuracy => 6,
+            Self::Mistake => 2,
+            Self::Blunder => 4,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shakmaty::Color;
+
+    #[test]
+    fn reads_scored_info_lines() {
+        let line = "info depth 12 seldepth 18 multipv 1 score cp 34 nodes 1000 pv e2e4 e7e5";
+        assert_eq!(
+            parse_info_line(line, Color::White),
+            Some(EngineLine {
+                depth: 12,
+                evaluation: Evaluation::Centipawns(34),
+                pv: vec!["e2e4".to_string(), "e7e5".to_string()],
+            })
+        );
+        let line = "info depth 20 score mate 3 pv d8h4";
+        assert_eq!(
+            parse_info_line(line, Color::Black).map(|line| line.evaluation),
+            Some(Evaluation::Mate(-3))
+        );
+        assert_eq!(
+            parse_info_line("info depth 30 score mate 0", Color::White).map(|line| line.evaluation),
+            Some(Evaluation::Centipawns(-MATE_SCORE))
+        );
+    }
+
+    #[test]
+    fn skips_lines_without_a_final_score() {
+        for line in [
+            "info depth 5 score cp 20 lowerbound pv e2e4",
+            "info depth 5 currmove e2e4 currmovenumber 1",
+            "info string NNUE enabled",
+            "bestmove e2e4 ponder e7e5",
+        ] {
+            assert_eq!(parse_info_line(line, Color::White), None, "{line}");
+        }
+    }
+
+    #[test]
+    fn judges_moves_by_the_movers_loss() {
+        let cp = Evaluation::Centipawns;
+        assert_eq!(
+            MoveJudgement::of_move(cp(30), cp(-300), Color::White),
+            Some(MoveJudgement::Blunder)
+        );
+        assert_eq!(
+            MoveJudgement::of_move(cp(30), cp(-100), Color::White),
+            Some(MoveJudgement::Mistake)
+        );
+        assert_eq!(
+            MoveJudgement::of_move(cp(0), cp(60), Color::Black),
+            Some(MoveJudgement::Inaccuracy)
+        );
+        assert_eq!(MoveJudgement::of_move(cp(0), cp(40), Color::Black), None);
+        assert_eq!(
+            MoveJudgement::of_move(cp(100), cp(-200), Color::Black),
+            None
+        );
+        // Letting a forced mate go is a blunder even when still winning
+        assert_eq!(
+            MoveJudgement::of_move(Evaluation::Mate(2), cp(500), Color::White),
+            Some(MoveJudgement::Blunder)
+        );
+        assert_eq!(Evaluation::Centipawns(-150).display(), "-1.50");
+        assert_eq!(Evaluation::Mate(-3).display(), "#-3");
+    }
+}
@@ -0,0 +1,25 @@
+/// Kind of broadcast notes. It's addressable, so each new note replaces the
+/// previous one with the same `d` tag and spectators only get the latest.
+pub const BROADCAST_KIND: u32 = 30_064;
+
+/// Content of a broadcast note, the game so far and the position it reached.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BroadcastContent {
+    pub pgn: String,
+    /// Position at the end of the main line.
+    pub fen: String,
+}
+
+impl std::str::FromStr for BroadcastContent {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl std::fmt::Display for BroadcastContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{json}")
+    }
+}
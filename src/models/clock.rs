@@ -0,0 +1,281 @@
+/// Time control of a game, written to the PGN `TimeControl` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TimeControl {
+    /// Starting time of each side, in seconds.
+    pub base: u32,
+    /// Seconds added after every move.
+    pub increment: u32,
+    /// Seconds every move can take before the clock starts running.
+    #[serde(default)]
+    pub delay: u32,
+}
+
+impl TimeControl {
+    #[must_use]
+    pub const fn new(base: u32, increment: u32, delay: u32) -> Self {
+        Self {
+            base,
+            increment,
+            delay,
+        }
+    }
+    /// Value of the PGN `TimeControl` header, like `"300+2"`.
+    ///
+    /// PGN has no notation for delays, they are written as `"300d5"` the way
+    /// most clock-aware tools do.
+    #[must_use]
+    pub fn pgn_value(self) -> String {
+        match (self.increment, self.delay) {
+            (0, 0) => self.base.to_string(),
+            (increment, 0) => format!("{}+{increment}", self.base),
+            (0, delay) => format!("{}d{delay}", self.base),
+            (increment, delay) => format!("{}+{increment}d{delay}", self.base),
+        }
+    }
+    /// Reads a `TimeControl` header with a single period. Unknown (`?`),
+    /// untimed (`-`) and multi-period controls give `None`.
+    #[must_use]
+    pub fn from_pgn_value(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (rest, delay) = match value.split_once('d') {
+            Some((rest, delay)) => (rest, delay.parse().ok()?),
+            None => (value, 0),
+        };
+        let (base, increment) = match rest.split_once('+') {
+            Some((base, increment)) => (base.parse().ok()?, increment.parse().ok()?),
+            None => (rest.parse().ok()?, 0),
+        };
+        Some(Self::new(base, increment, delay))
+    }
+}
+
+/// Remaining time as written in a `%clk` command, `h:mm:ss`.
+#[must_use]
+pub fn format_clock(millis: u64) -> String {
+    let seconds = millis / 1000;
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds / 60) % 60,
+        seconds % 60
+    )
+}
+
+/// Splits the `[%clk h:mm:ss]` command out of a PGN comment.
+/// Returns the remaining time it holds and the rest of the comment.
+#[must_use]
+pub fn take_clock_command(comment: &str) -> (Option<u64>, String) {
+    let Some(start) = comment.find("[%clk") else {
+        return (None, comment.to_string());
+    };
+    let Some(length) = comment[start..].find(']') else {
+        return (None, comment.to_string());
+    };
+    let value = comment[start + "[%clk".len()..start + length].trim();
+    let mut parts = value.split(':').rev();
+    let seconds = parts.next().and_then(|part| part.parse::<f64>().ok());
+    let minutes = parts
+        .next()
+        .map_or(Some(0), |part| part.parse::<u64>().ok());
+    let hours = parts
+        .next()
+        .map_or(Some(0), |part| part.parse::<u64>().ok());
+    let millis = match (hours, minutes, seconds) {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        (Some(hours), Some(minutes), Some(seconds)) => {
+            Some((hours * 3600 + minutes * 60) * 1000 + (seconds * 1000.0) as u64)
+        }
+        _ => None,
+    };
+    let rest = format!("{}{}", &comment[..start], &comment[start + length + 1..]);
+    (millis, rest.trim().to_string())
+}
+
+/// Milliseconds since the epoch, as used to run the clock.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn now_millis() -> u64 {
+    web_sys::js_sys::Date::now() as u64
+}
+
+/// Two-sided chess clock.
+///
+/// Times are only counted when the clock is paused or pressed, in between the
+/// remaining time is computed from when the running side started thinking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChessClock {
+    time_control: TimeControl,
+    white: u64,
+    black: u64,
+    /// Side whose time is running and when its turn started, `None` while paused.
+    running: Option<(shakmaty::Color, u64)>,
+}
+
+impl ChessClock {
+    #[must_use]
+    pub fn new(time_control: TimeControl) -> Self {
+        let base = u64::from(time_control.base) * 1000;
+        Self {
+            time_control,
+            white: base,
+            black: base,
+            running: None,
+        }
+    }
+    /// Clock of a game already under way, with the last recorded times.
+    #[must_use]
+    pub fn resumed(time_control: TimeControl, white: Option<u64>, black: Option<u64>) -> Self {
+        let clock = Self::new(time_control);
+        Self {
+            white: white.unwrap_or(clock.white),
+            black: black.unwrap_or(clock.black),
+            ..clock
+        }
+    }
+    #[must_use]
+    pub const fn time_control(&self) -> TimeControl {
+        self.time_control
+    }
+    #[must_use]
+    pub fn running_side(&self) -> Option<shakmaty::Color> {
+        self.running.map(|(color, _)| color)
+    }
+    /// Time left to `color` at `now`, in milliseconds.
+    #[must_use]
+    pub fn remaining(&self, color: shakmaty::Color, now: u64) -> u64 {
+        let stored = match color {
+            shakmaty::Color::White => self.white,
+            shakmaty::Color::Black => self.black,
+        };
+        match self.running {
+            Some((running, started)) if running == color => {
+                let delay = u64::from(self.time_control.delay) * 1000;
+                let spent = now.saturating_sub(started).saturating_sub(delay);
+                stored.saturating_sub(spent)
+            }
+            _ => stored,
+        }
+    }
+    /// Starts the time of `color`, counting the time of a side that was running.
+    #[must_use]
+    pub fn start(&self, color: shakmaty::Color, now: u64) -> Self {
+        Self {
+            running: Some((color, now)),
+            ..self.paused(now)
+        }
+    }
+    #[must_use]
+    pub fn paused(&self, now: u64) -> Self {
+        Self {
+            white: self.remaining(shakmaty::Color::White, now),
+            black: self.remaining(shakmaty::Color::Black, now),
+            running: None,
+            ..self.clone()
+        }
+    }
+    /// Ends the turn of `color`: the time since the clock last started is
+    /// counted to it, the increment added and the opponent's time starts.
+    /// Returns the new clock and the time left to the side that moved,
+    /// `None` while paused.
+    #[must_use]
+    pub fn pressed(&self, color: shakmaty::Color, now: u64) -> Option<(Self, u64)> {
+        let (_, started) = self.running?;
+        let mut clock = Self {
+            running: Some((color, started)),
+            ..self.clone()
+        }
+        .paused(now);
+        let increment = u64::from(self.time_control.increment) * 1000;
+        let remaining = match color {
+            shakmaty::Color::White => &mut clock.white,
+            shakmaty::Color::Black => &mut clock.black,
+        };
+        // A flagged side stays at zero, the result is up to the arbiter
+        if *remaining > 0 {
+            *remaining += increment;
+        }
+        let left = *remaining;
+        Some((clock.start(color.other(), now), left))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shakmaty::Color;
+
+    #[test]
+    fn reads_time_control_headers() {
+        assert_eq!(
+            TimeControl::from_pgn_value("300"),
+            Some(TimeControl::new(300, 0, 0))
+        );
+        assert_eq!(
+            TimeControl::from_pgn_value("600+5d3"),
+            Some(TimeControl::new(600, 5, 3))
+        );
+        assert_eq!(TimeControl::from_pgn_value("?"), None);
+        assert_eq!(TimeControl::from_pgn_value("-"), None);
+        assert_eq!(TimeControl::from_pgn_value("40/7200:3600"), None);
+        for control in [
+            TimeControl::new(180, 2, 0),
+            TimeControl::new(60, 0, 5),
+            TimeControl::new(600, 5, 3),
+        ] {
+            assert_eq!(
+                TimeControl::from_pgn_value(&control.pgn_value()),
+                Some(control)
+            );
+        }
+    }
+
+    #[test]
+    fn splits_clock_commands_out_of_comments() {
+        assert_eq!(
+            take_clock_command("[%clk 1:02:03] good move"),
+            (Some(3_723_000), "good move".to_string())
+        );
+        assert_eq!(
+            take_clock_command("only move [%clk 0:00:09.5]"),
+            (Some(9_500), "only move".to_string())
+        );
+        assert_eq!(
+            take_clock_command("[%clk 4:05]"),
+            (Some(245_000), String::new())
+        );
+        assert_eq!(
+            take_clock_command("no clock"),
+            (None, "no clock".to_string())
+        );
+        assert_eq!(format_clock(3_723_000), "1:02:03");
+    }
+
+    #[test]
+    fn pressing_charges_the_side_that_moved() {
+        let clock = ChessClock::new(TimeControl::new(60, 2, 0)).start(Color::White, 0);
+        assert_eq!(clock.remaining(Color::White, 10_000), 50_000);
+        let (clock, left) = clock.pressed(Color::White, 10_000).unwrap();
+        assert_eq!(left, 52_000);
+        assert_eq!(clock.running_side(), Some(Color::Black));
+        // Still running for Black after a take back, the time goes to White who moved
+        let (clock, left) = clock.pressed(Color::White, 15_000).unwrap();
+        assert_eq!(left, 49_000);
+        assert_eq!(clock.remaining(Color::Black, 15_000), 60_000);
+        assert_eq!(clock.running_side(), Some(Color::Black));
+        assert_eq!(
+            ChessClock::new(TimeControl::new(60, 2, 0)).pressed(Color::White, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn delay_and_flags_are_respected() {
+        let clock = ChessClock::new(TimeControl::new(60, 0, 5)).start(Color::White, 0);
+        assert_eq!(clock.remaining(Color::White, 4_000), 60_000);
+        let (_, left) = clock.pressed(Color::White, 8_000).unwrap();
+        assert_eq!(left, 57_000);
+        let flagged = ChessClock::new(TimeControl::new(1, 2, 0)).start(Color::White, 0);
+        let (_, left) = flagged.pressed(Color::White, 5_000).unwrap();
+        assert_eq!(left, 0);
+    }
+}
@@ -0,0 +1,617 @@
+use shakmaty::Position;
+
+/// Index of a move inside a [`GameTree`].
+/// The root node (the starting position) is always [`GameTree::ROOT`].
+pub type MoveId = usize;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveNode {
+    pub parent: Option<MoveId>,
+    /// The first child is the main continuation, any other child is a variation.
+    pub children: Vec<MoveId>,
+    /// `None` only for the root node.
+    pub san: Option<shakmaty::san::SanPlus>,
+    /// Position reached after playing `san`.
+    pub position: shakmaty::variant::VariantPosition,
+    /// Comment written after the move, or before the first move for the root.
+    pub comment: Option<String>,
+    pub nags: Vec<u8>,
+    /// Time left to the side that moved, in milliseconds, from a `%clk` command.
+    pub clock: Option<u64>,
+}
+
+/// Serializable form of a [`GameTree`], used to persist the live game.
+/// Moves detached by deleting a variation are not kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StoredTree {
+    #[serde(default)]
+    pub variant: super::GameVariant,
+    pub start_fen: String,
+    /// Comment before the first move.
+    pub comment: Option<String>,
+    /// A parent always comes before its children, and siblings keep their order.
+    pub moves: Vec<StoredMove>,
+    /// Index of the cursor in `moves` plus one, 0 being the starting position.
+    pub cursor: usize,
+}
+
+impl StoredTree {
+    /// Number of moves on the main line, variations left out.
+    #[must_use]
+    pub fn mainline_len(&self) -> usize {
+        let mut len = 0;
+        let mut current = 0;
+        // The main continuation is stored before its siblings
+        while let Some(index) = self
+            .moves
+            .iter()
+            .position(|stored| stored.parent == current)
+        {
+            len += 1;
+            current = index + 1;
+        }
+        len
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StoredMove {
+    /// Index of the parent in [`StoredTree::moves`] plus one, 0 being the starting position.
+    pub parent: usize,
+    pub san: String,
+    pub comment: Option<String>,
+    pub nags: Vec<u8>,
+    #[serde(default)]
+    pub clock: Option<u64>,
+}
+
+/// Move tree of an annotated game.
+///
+/// Nodes are stored in an arena and never removed, deleting a variation only
+/// detaches it from its parent so every `MoveId` handed out stays valid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameTree {
+    nodes: Vec<MoveNode>,
+}
+
+impl Default for GameTree {
+    fn default() -> Self {
+        Self::new(shakmaty::variant::VariantPosition::new(
+            shakmaty::variant::Variant::Chess,
+        ))
+    }
+}
+
+impl GameTree {
+    pub const ROOT: MoveId = 0;
+
+    #[must_use]
+    pub fn new(start: shakmaty::variant::VariantPosition) -> Self {
+        Self {
+            nodes: vec![MoveNode {
+                parent: None,
+                children: vec![],
+                san: None,
+                position: start,
+                comment: None,
+                nags: vec![],
+                clock: None,
+            }],
+        }
+    }
+    #[must_use]
+    pub fn get(&self, id: MoveId) -> Option<&MoveNode> {
+        self.nodes.get(id)
+    }
+    #[must_use]
+    pub fn position(&self, id: MoveId) -> shakmaty::variant::VariantPosition {
+        self.nodes
+            .get(id)
+            .unwrap_or(&self.nodes[Self::ROOT])
+            .position
+            .clone()
+    }
+    #[must_use]
+    pub fn children(&self, id: MoveId) -> &[MoveId] {
+        self.nodes
+            .get(id)
+            .map(|node| node.children.as_slice())
+            .unwrap_or_default()
+    }
+    #[must_use]
+    pub fn parent(&self, id: MoveId) -> Option<MoveId> {
+        self.nodes.get(id).and_then(|node| node.parent)
+    }
+    /// Child of `parent` reached by playing `mv`, if it is already in the tree.
+    #[must_use]
+    pub fn find_child(&self, parent: MoveId, mv: &shakmaty::Move) -> Option<MoveId> {
+        let parent_position = self.nodes.get(parent)?.position.clone();
+        let san = shakmaty::san::SanPlus::from_move(parent_position, mv);
+        self.children(parent)
+            .iter()
+            .copied()
+            .find(|child| self.nodes[*child].san.as_ref() == Some(&san))
+    }
+    /// Plays `mv` after `parent` and returns the id of the new node.
+    ///
+    /// If `parent` already has a child with the same move, that child is
+    /// returned instead of creating a duplicate variation.
+    pub fn play(&mut self, parent: MoveId, mv: &shakmaty::Move) -> Option<MoveId> {
+        if let Some(existing) = self.find_child(parent, mv) {
+            return Some(existing);
+        }
+        let parent_position = self.nodes.get(parent)?.position.clone();
+        let san = shakmaty::san::SanPlus::from_move(parent_position.clone(), mv);
+        let position = parent_position.play(mv).ok()?;
+        let id = self.nodes.len();
+        self.nodes.push(MoveNode {
+            parent: Some(parent),
+            children: vec![],
+            san: Some(san),
+            position,
+            comment: None,
+            nags: vec![],
+            clock: None,
+        });
+        self.nodes[parent].children.push(id);
+        Some(id)
+    }
+    /// Plays `mv` after `parent`, replacing the main continuation of `parent`.
+    pub fn overwrite(&mut self, parent: MoveId, mv: &shakmaty::Move) -> Option<MoveId> {
+        let continuation = self.children(parent).first().copied();
+        let id = self.play(parent, mv)?;
+        if let Some(continuation) = continuation.filter(|continuation| *continuation != id) {
+            self.delete_variation(continuation);
+        }
+        let children = &mut self.nodes[parent].children;
+        children.retain(|child| *child != id);
+        children.insert(0, id);
+        Some(id)
+    }
+    /// Follows the main continuation of every node after `from`, `from` excluded.
+    #[must_use]
+    pub fn line_from(&self, from: MoveId) -> Vec<MoveId> {
+        let mut line = vec![];
+        let mut current = from;
+        while let Some(next) = self.children(current).first().copied() {
+            line.push(next);
+            current = next;
+        }
+        line
+    }
+    #[must_use]
+    pub fn mainline(&self) -> Vec<MoveId> {
+        self.line_from(Self::ROOT)
+    }
+    #[must_use]
+    pub fn line_end(&self, from: MoveId) -> MoveId {
+        self.line_from(from).last().copied().unwrap_or(from)
+    }
+    #[must_use]
+    pub fn mainline_end(&self) -> MoveId {
+        self.line_end(Self::ROOT)
+    }
+    #[must_use]
+    pub fn mainline_sans(&self) -> Vec<shakmaty::san::SanPlus> {
+        self.mainline()
+            .into_iter()
+            .filter_map(|id| self.nodes[id].san.clone())
+            .collect()
+    }
+    /// Moves leading from the starting position to `id`, root excluded.
+    #[must_use]
+    pub fn path_to(&self, id: MoveId) -> Vec<MoveId> {
+        let mut path = vec![];
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            if node_id == Self::ROOT {
+                break;
+            }
+            path.push(node_id);
+            current = self.parent(node_id);
+        }
+        path.reverse();
+        path
+    }
+    #[must_use]
+    pub fn is_mainline(&self, id: MoveId) -> bool {
+        self.path_to(id).into_iter().all(|node_id| {
+            self.parent(node_id)
+                .and_then(|parent| self.children(parent).first().copied())
+                == Some(node_id)
+        })
+    }
+    /// First move of the innermost variation containing `id`, the closest move
+    /// on its path that isn't its parent's main continuation.
+    /// `None` on the main line.
+    #[must_use]
+    pub fn variation_start(&self, id: MoveId) -> Option<MoveId> {
+        self.path_to(id).into_iter().rev().find(|node_id| {
+            self.parent(*node_id)
+                .and_then(|parent| self.children(parent).first().copied())
+                != Some(*node_id)
+        })
+    }
+    /// Moves the variation containing `id` up to the main line,
+    /// demoting the previous main continuations to variations.
+    pub fn promote_variation(&mut self, id: MoveId) {
+        let mut current = id;
+        while let Some(parent) = self.parent(current) {
+            let children = &mut self.nodes[parent].children;
+            if let Some(index) = children.iter().position(|child| *child == current) {
+                let promoted = children.remove(index);
+                children.insert(0, promoted);
+            }
+            current = parent;
+        }
+    }
+    /// Detaches `id` and everything after it from the tree.
+    /// Returns the parent of the deleted move.
+    pub fn delete_variation(&mut self, id: MoveId) -> Option<MoveId> {
+        let parent = self.parent(id)?;
+        self.nodes[parent].children.retain(|child| *child != id);
+        Some(parent)
+    }
+    /// Sets the comment of `id`, an empty comment clears it.
+    pub fn set_comment(&mut self, id: MoveId, comment: &str) {
+        if let Some(node) = self.nodes.get_mut(id) {
+            let comment = comment.trim();
+            node.comment = (!comment.is_empty()).then(|| comment.to_string());
+        }
+    }
+    /// Adds `nag` to `id`, replacing any glyph of the same group, or removes it if already set.
+    pub fn toggle_nag(&mut self, id: MoveId, nag: u8) {
+        let Some(node) = self.nodes.get_mut(id) else {
+            return;
+        };
+        if node.nags.contains(&nag) {
+            node.nags.retain(|existing| *existing != nag);
+            return;
+        }
+        let group = super::nag_group(nag);
+        if group != super::NagGroup::Other {
+            node.nags
+                .retain(|existing| super::nag_group(*existing) != group);
+        }
+        node.nags.push(nag);
+        node.nags.sort_unstable();
+    }
+    pub fn clear_nags(&mut self, id: MoveId) {
+        if let Some(node) = self.nodes.get_mut(id) {
+            node.nags.clear();
+        }
+    }
+    pub fn set_clock(&mut self, id: MoveId, clock: Option<u64>) {
+        if let Some(node) = self.nodes.get_mut(id).filter(|node| node.san.is_some()) {
+            node.clock = clock;
+        }
+    }
+    /// Last time recorded for `color` on the line leading to `id`.
+    #[must_use]
+    pub fn last_clock(&self, id: MoveId, color: shakmaty::Color) -> Option<u64> {
+        self.path_to(id)
+            .into_iter()
+            .rev()
+            .filter(|node_id| self.move_number(*node_id).1 == color)
+            .find_map(|node_id| self.nodes[node_id].clock)
+    }
+    /// Times the position at `id` occurred on the line leading to it, itself included.
+    #[must_use]
+    pub fn repetitions(&self, id: MoveId) -> usize {
+        let target = repetition_key(&self.position(id));
+        std::iter::once(Self::ROOT)
+            .chain(self.path_to(id))
+            .filter(|node_id| repetition_key(&self.nodes[*node_id].position) == target)
+            .count()
+    }
+    /// Draw the side to move at `id` may claim, by threefold repetition or the
+    /// fifty-move rule.
+    #[must_use]
+    pub fn claimable_draw(&self, id: MoveId) -> Option<super::TerminationReason> {
+        if self.repetitions(id) >= 3 {
+            Some(super::TerminationReason::ThreefoldRepetition)
+        } else if self.position(id).halfmoves() >= 100 {
+            Some(super::TerminationReason::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+    #[must_use]
+    pub fn variant(&self) -> super::GameVariant {
+        super::GameVariant::of_position(&self.nodes[Self::ROOT].position)
+    }
+    /// FEN of the starting position, `None` for the usual one of the variant.
+    /// Chess960 games always have one, since the back ranks differ between games.
+    #[must_use]
+    pub fn setup_fen(&self) -> Option<String> {
+        let variant = self.variant();
+        let fen = position_fen(&self.nodes[Self::ROOT].position);
+        (variant == super::GameVariant::Chess960 || fen != position_fen(&variant.start_position()))
+            .then_some(fen)
+    }
+    /// Snapshot of every move reachable from the root, with `cursor` as the current move.
+    #[must_use]
+    pub fn to_stored(&self, cursor: MoveId) -> StoredTree {
+        let mut stored = StoredTree {
+            variant: self.variant(),
+            start_fen: position_fen(&self.nodes[Self::ROOT].position),
+            comment: self.nodes[Self::ROOT].comment.clone(),
+            moves: vec![],
+            cursor: 0,
+        };
+        let mut pending = vec![(Self::ROOT, 0)];
+        while let Some((id, index)) = pending.pop() {
+            if id == cursor {
+                stored.cursor = index;
+            }
+            // Siblings are stored next to each other so the main continuation is replayed first
+            let mut children = vec![];
+            for child in self.children(id) {
+                let node = &self.nodes[*child];
+                stored.moves.push(StoredMove {
+                    parent: index,
+                    san: node
+                        .san
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                    comment: node.comment.clone(),
+                    nags: node.nags.clone(),
+                    clock: node.clock,
+                });
+                children.push((*child, stored.moves.len()));
+            }
+            pending.extend(children.into_iter().rev());
+        }
+        stored
+    }
+    /// Rebuilds a tree from a snapshot, returning it with the stored cursor.
+    /// Returns `None` if the snapshot holds an invalid position or an illegal move.
+    #[must_use]
+    pub fn from_stored(stored: &StoredTree) -> Option<(Self, MoveId)> {
+        let start = super::position_from_fen(&stored.start_fen, stored.variant).ok()?;
+        let mut tree = Self::new(start);
+        tree.nodes[Self::ROOT].comment.clone_from(&stored.comment);
+        let mut ids = vec![Self::ROOT];
+        for stored_move in &stored.moves {
+            let parent = *ids.get(stored_move.parent)?;
+            let san = stored_move.san.parse::<shakmaty::san::SanPlus>().ok()?;
+            let mv = san.san.to_move(&tree.nodes[parent].position).ok()?;
+            let id = tree.play(parent, &mv)?;
+            tree.nodes[id].comment.clone_from(&stored_move.comment);
+            tree.nodes[id].nags.clone_from(&stored_move.nags);
+            tree.nodes[id].clock = stored_move.clock;
+            ids.push(id);
+        }
+        let cursor = ids.get(stored.cursor).copied().unwrap_or(Self::ROOT);
+        Some((tree, cursor))
+    }
+    /// Full move number of `id` and the side that played it.
+    #[must_use]
+    pub fn move_number(&self, id: MoveId) -> (u32, shakmaty::Color) {
+        let before = &self
+            .parent(id)
+            .and_then(|parent| self.nodes.get(parent))
+            .unwrap_or(&self.nodes[Self::ROOT])
+            .position;
+        (before.fullmoves().get(), before.turn())
+    }
+    /// Move number prefix as written in PGN, `"1."` for white and `"1..."` for
+    /// black when `force_number` is set. Black moves continuing a line get none.
+    #[must_use]
+    pub fn move_number_prefix(&self, id: MoveId, force_number: bool) -> Option<String> {
+        match self.move_number(id) {
+            (number, shakmaty::Color::White) => Some(format!("{number}.")),
+            (number, shakmaty::Color::Black) if force_number => Some(format!("{number}...")),
+            _ => None,
+        }
+    }
+    /// Movetext of the whole tree with variations as nested RAV sections.
+    #[must_use]
+    pub fn to_movetext(&self) -> String {
+        let mut tokens = vec![];
+        if let Some(comment) = &self.nodes[Self::ROOT].comment {
+            tokens.push(pgn_comment(comment));
+        }
+        self.write_line(Self::ROOT, true, &mut tokens);
+        tokens.join(" ")
+    }
+
+    fn write_line(&self, parent: MoveId, force_number: bool, tokens: &mut Vec<String>) {
+        let mut current = parent;
+        let mut force_number = force_number;
+        while let Some((main, variations)) = self.children(current).split_first() {
+            tokens.push(self.move_token(*main, force_number));
+            for variation in variations {
+                let mut inner = vec![self.move_token(*variation, true)];
+                self.write_line(*variation, self.interrupts_line(*variation), &mut inner);
+                tokens.push(format!("({})", inner.join(" ")));
+            }
+            force_number = !variations.is_empty() || self.interrupts_line(*main);
+            current = *main;
+        }
+    }
+
+    /// Whether a comment or clock follows `id`, so the next move needs its number again.
+    fn interrupts_line(&self, id: MoveId) -> bool {
+        let node = &self.nodes[id];
+        node.comment.is_some() || node.clock.is_some()
+    }
+
+    fn move_token(&self, id: MoveId, force_number: bool) -> String {
+        let san = self.nodes[id]
+            .san
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let mut token = match self.move_number_prefix(id, force_number) {
+            Some(prefix) => format!("{prefix} {san}"),
+            None => san,
+        };
+        for nag in &self.nodes[id].nags {
+            token.push_str(&format!(" ${nag}"));
+        }
+        let clock = self.nodes[id]
+            .clock
+            .map(|clock| format!("[%clk {}]", super::format_clock(clock)));
+        let comment = match (clock, &self.nodes[id].comment) {
+            (Some(clock), Some(comment)) => Some(format!("{clock} {comment}")),
+            (clock, comment) => clock.or_else(|| comment.clone()),
+        };
+        if let Some(comment) = comment {
+            token.push(' ');
+            token.push_str(&pgn_comment(&comment));
+        }
+        token
+    }
+}
+
+/// PGN comments end at the first `}`, so it can't appear inside one.
+fn pgn_comment(comment: &str) -> String {
+    format!("{{{}}}", comment.replace('}', ")"))
+}
+
+fn position_fen(position: &shakmaty::variant::VariantPosition) -> String {
+    shakmaty::fen::Fen::from_position(position.clone(), shakmaty::EnPassantMode::Legal).to_string()
+}
+
+/// Position without its move counters, positions repeat when these are equal.
+fn repetition_key(position: &shakmaty::variant::VariantPosition) -> shakmaty::Setup {
+    let mut setup = position.clone().into_setup(shakmaty::EnPassantMode::Legal);
+    setup.halfmoves = 0;
+    setup.fullmoves = std::num::NonZeroU32::MIN;
+    setup
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays `san` after `parent`, which must be a legal move there.
+    fn play(tree: &mut GameTree, parent: MoveId, san: &str) -> MoveId {
+        let mv = san
+            .parse::<shakmaty::san::San>()
+            .unwrap()
+            .to_move(&tree.position(parent))
+            .unwrap();
+        tree.play(parent, &mv).unwrap()
+    }
+
+    fn sans(tree: &GameTree, ids: &[MoveId]) -> Vec<String> {
+        ids.iter()
+            .filter_map(|id| tree.get(*id)?.san.as_ref().map(ToString::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn plays_moves_without_duplicating_them() {
+        let mut tree = GameTree::default();
+        let e4 = play(&mut tree, GameTree::ROOT, "e4");
+        let d4 = play(&mut tree, GameTree::ROOT, "d4");
+        assert_eq!(play(&mut tree, GameTree::ROOT, "e4"), e4);
+        assert_eq!(tree.children(GameTree::ROOT), &[e4, d4]);
+        assert!(tree.is_mainline(e4));
+        assert!(!tree.is_mainline(d4));
+        let e5 = play(&mut tree, e4, "e5");
+        assert_eq!(tree.move_number(e5), (1, shakmaty::Color::Black));
+        assert_eq!(tree.path_to(e5), vec![e4, e5]);
+        assert_eq!(tree.mainline_end(), e5);
+    }
+
+    #[test]
+    fn overwrite_replaces_the_main_continuation() {
+        let mut tree = GameTree::default();
+        let e4 = play(&mut tree, GameTree::ROOT, "e4");
+        let e5 = play(&mut tree, e4, "e5");
+        play(&mut tree, e5, "Nf3");
+        let c5 = play(&mut tree, e4, "c5");
+        let e6 = "e6"
+            .parse::<shakmaty::san::San>()
+            .unwrap()
+            .to_move(&tree.position(e4))
+            .unwrap();
+        let e6 = tree.overwrite(e4, &e6).unwrap();
+        // The old main line is gone, the variation is kept
+        assert_eq!(tree.children(e4), &[e6, c5]);
+        assert_eq!(sans(&tree, &tree.mainline()), ["e4", "e6"]);
+        // Overwriting with the move of a variation moves it up instead
+        let c5_move = "c5"
+            .parse::<shakmaty::san::San>()
+            .unwrap()
+            .to_move(&tree.position(e4))
+            .unwrap();
+        assert_eq!(tree.overwrite(e4, &c5_move), Some(c5));
+        assert_eq!(tree.children(e4), &[c5]);
+    }
+
+    #[test]
+    fn promotes_and_deletes_variations() {
+        let mut tree = GameTree::default();
+        let e4 = play(&mut tree, GameTree::ROOT, "e4");
+        let e5 = play(&mut tree, e4, "e5");
+        let c5 = play(&mut tree, e4, "c5");
+        let nf3 = play(&mut tree, c5, "Nf3");
+        let nc3 = play(&mut tree, c5, "Nc3");
+        tree.promote_variation(nc3);
+        assert_eq!(tree.mainline(), vec![e4, c5, nc3]);
+        assert_eq!(tree.children(e4), &[c5, e5]);
+        assert_eq!(tree.children(c5), &[nc3, nf3]);
+        let e6 = play(&mut tree, nf3, "e6");
+        assert_eq!(tree.variation_start(e6), Some(nf3));
+        assert_eq!(tree.variation_start(e5), Some(e5));
+        assert_eq!(tree.variation_start(nc3), None);
+
+        assert_eq!(tree.delete_variation(c5), Some(e4));
+        assert_eq!(tree.mainline(), vec![e4, e5]);
+        // Deleted moves keep their id
+        assert_eq!(sans(&tree, &[nf3]), ["Nf3"]);
+        assert_eq!(tree.delete_variation(GameTree::ROOT), None);
+    }
+
+    #[test]
+    fn stored_trees_keep_variations() {
+        let mut tree = GameTree::default();
+        let e4 = play(&mut tree, GameTree::ROOT, "e4");
+        let e5 = play(&mut tree, e4, "e5");
+        let c5 = play(&mut tree, e4, "c5");
+        let nf3 = play(&mut tree, c5, "Nf3");
+        play(&mut tree, e5, "Nf3");
+        tree.set_comment(c5, "sharper");
+        let stored = tree.to_stored(nf3);
+        assert_eq!(stored.moves.len(), 5);
+        assert_eq!(stored.mainline_len(), 3);
+
+        let (restored, cursor) = GameTree::from_stored(&stored).unwrap();
+        assert_eq!(restored.to_movetext(), tree.to_movetext());
+        assert_eq!(
+            sans(&restored, &restored.path_to(cursor)),
+            ["e4", "c5", "Nf3"]
+        );
+    }
+
+    #[test]
+    fn writes_variations_as_rav() {
+        let mut tree = GameTree::default();
+        let e4 = play(&mut tree, GameTree::ROOT, "e4");
+        let e5 = play(&mut tree, e4, "e5");
+        let nf3 = play(&mut tree, e5, "Nf3");
+        let c5 = play(&mut tree, e4, "c5");
+        play(&mut tree, c5, "Nf3");
+        let bc4 = play(&mut tree, e5, "Bc4");
+        play(&mut tree, nf3, "Nc6");
+        tree.toggle_nag(nf3, 1);
+        tree.set_comment(e4, "best by test");
+        assert_eq!(
+            tree.to_movetext(),
+            "1. e4 {best by test} 1... e5 (1... c5 2. Nf3) 2. Nf3 $1 (2. Bc4) 2... Nc6"
+        );
+        // A comment on the first move of a variation numbers the next one too
+        play(&mut tree, bc4, "Nc6");
+        tree.set_comment(bc4, "idea");
+        assert_eq!(
+            tree.to_movetext(),
+            "1. e4 {best by test} 1... e5 (1... c5 2. Nf3) 2. Nf3 $1 (2. Bc4 {idea} 2... Nc6) 2... Nc6"
+        );
+        assert_eq!(GameTree::default().to_movetext(), "");
+    }
+}
@@ -1,2 +1,28 @@
+mod analysis;
+mod broadcast;
+mod clock;
+mod game_tree;
+mod nag;
+mod nip19;
+mod nip46;
 mod nostr_metadata;
+mod notation;
+mod pgn_import;
+mod recipient;
+mod signer;
+mod termination;
+mod variant;
+pub use analysis::*;
+pub use broadcast::*;
+pub use clock::*;
+pub use game_tree::*;
+pub use nag::*;
+pub use nip19::Naddr;
+pub use nip46::*;
 pub use nostr_metadata::*;
+pub use notation::*;
+pub use pgn_import::*;
+pub use recipient::*;
+pub use signer::*;
+pub use termination::*;
+pub use variant::*;
@@ -0,0 +1,44 @@
+/// Numeric Annotation Glyphs offered by the annotator, with their usual symbol.
+///
+/// Move assessments (`$1`-`$6`) and position assessments (`$10`-`$19`) are
+/// mutually exclusive within their group, see [`nag_group`].
+pub const NAGS: [(u8, &str); 14] = [
+    (1, "!"),
+    (2, "?"),
+    (3, "!!"),
+    (4, "??"),
+    (5, "!?"),
+    (6, "?!"),
+    (10, "="),
+    (13, "∞"),
+    (14, "+="),
+    (15, "=+"),
+    (16, "±"),
+    (17, "∓"),
+    (18, "+-"),
+    (19, "-+"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NagGroup {
+    Move,
+    Position,
+    Other,
+}
+
+#[must_use]
+pub const fn nag_group(nag: u8) -> NagGroup {
+    match nag {
+        1..=9 => NagGroup::Move,
+        10..=19 => NagGroup::Position,
+        _ => NagGroup::Other,
+    }
+}
+
+/// Symbol for `nag`, falling back to the PGN `$n` notation for glyphs without one.
+#[must_use]
+pub fn nag_glyph(nag: u8) -> String {
+    NAGS.iter()
+        .find(|(value, _)| *value == nag)
+        .map_or_else(|| format!("${nag}"), |(_, glyph)| (*glyph).to_string())
+}
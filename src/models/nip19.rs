@@ -0,0 +1,143 @@
+//! Bech32 entities of NIP-19, the `npub`, `nprofile` and `naddr` strings shared between clients.
+
+/// `naddr` TLV entries.
+const TLV_IDENTIFIER: u8 = 0;
+const TLV_RELAY: u8 = 1;
+const TLV_AUTHOR: u8 = 2;
+const TLV_KIND: u8 = 3;
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn hex_bytes(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+/// Human readable part, in lowercase, and payload bytes of a bech32 string,
+/// `None` if it isn't valid bech32.
+pub(crate) fn bech32_decode(s: &str) -> Option<(String, Vec<u8>)> {
+    let (hrp, data) = bech32::decode(s).ok()?;
+    Some((hrp.to_lowercase(), data))
+}
+
+/// Bech32 string of `bytes` under the human readable part `hrp`, `None` if
+/// the part isn't valid or the string gets too long.
+pub(crate) fn bech32_encode(hrp: &str, bytes: &[u8]) -> Option<String> {
+    let hrp = bech32::Hrp::parse(hrp).ok()?;
+    bech32::encode::<bech32::Bech32>(hrp, bytes).ok()
+}
+
+/// Type and value of each entry of a TLV payload, `None` if one is cut short.
+pub(crate) fn tlv_entries(data: &[u8]) -> Option<Vec<(u8, &[u8])>> {
+    let mut entries = vec![];
+    let mut rest = data;
+    while let [kind, length, tail @ ..] = rest {
+        let value = tail.get(..usize::from(*length))?;
+        entries.push((*kind, value));
+        rest = &tail[value.len()..];
+    }
+    rest.is_empty().then_some(entries)
+}
+
+/// Address of a replaceable event, shared as an `naddr` string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Naddr {
+    /// Value of the `d` tag.
+    pub identifier: String,
+    /// Author pubkey, in hex.
+    pub pubkey: String,
+    pub kind: u32,
+    /// Relays the event can be found on.
+    pub relays: Vec<String>,
+}
+
+impl std::fmt::Display for Naddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut data = vec![];
+        let mut push = |kind: u8, value: &[u8]| {
+            // TLV lengths are a single byte, longer values can't be shared
+            if let Ok(length) = u8::try_from(value.len()) {
+                data.push(kind);
+                data.push(length);
+                data.extend_from_slice(value);
+            }
+        };
+        push(TLV_IDENTIFIER, self.identifier.as_bytes());
+        for relay in &self.relays {
+            push(TLV_RELAY, relay.as_bytes());
+        }
+        push(TLV_AUTHOR, &hex_bytes(&self.pubkey).unwrap_or_default());
+        push(TLV_KIND, &self.kind.to_be_bytes());
+        let encoded = bech32_encode("naddr", &data).ok_or(std::fmt::Error)?;
+        write!(f, "{encoded}")
+    }
+}
+
+impl std::str::FromStr for Naddr {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (hrp, data) = bech32_decode(s.strip_prefix("nostr:").unwrap_or(s)).ok_or(())?;
+        if hrp != "naddr" {
+            return Err(());
+        }
+        let (mut identifier, mut pubkey, mut kind, mut relays) = (None, None, None, vec![]);
+        for (entry, value) in tlv_entries(&data).ok_or(())? {
+            match entry {
+                TLV_IDENTIFIER => {
+                    identifier = Some(String::from_utf8(value.to_vec()).map_err(|_| ())?);
+                }
+                TLV_RELAY => relays.extend(String::from_utf8(value.to_vec()).ok()),
+                TLV_AUTHOR if value.len() == 32 => pubkey = Some(hex(value)),
+                TLV_KIND => kind = Some(u32::from_be_bytes(value.try_into().map_err(|_| ())?)),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            identifier: identifier.ok_or(())?,
+            pubkey: pubkey.ok_or(())?,
+            kind: kind.ok_or(())?,
+            relays,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_like_other_clients() {
+        let pubkey =
+            hex_bytes("7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e").unwrap();
+        assert_eq!(
+            bech32_encode("npub", &pubkey).as_deref(),
+            Some("npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptg")
+        );
+        assert_eq!(
+            bech32_decode("npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptg"),
+            Some(("npub".to_string(), pubkey))
+        );
+    }
+
+    #[test]
+    fn naddr_round_trips() {
+        let naddr = Naddr {
+            identifier: "rooky-1700000000".to_string(),
+            pubkey: "3bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d".to_string(),
+            kind: 30_064,
+            relays: vec!["wss://nos.lol".to_string()],
+        };
+        let encoded = naddr.to_string();
+        assert!(encoded.starts_with("naddr1"));
+        assert_eq!(encoded.parse(), Ok(naddr));
+        assert_eq!("npub1xyz".parse::<Naddr>(), Err(()));
+    }
+}
@@ -0,0 +1,230 @@
+//! NIP-46 remote signing: the URIs a signer is reached with and the messages
+//! exchanged with it.
+
+use std::str::FromStr;
+
+use super::recipient::is_hex_pubkey;
+
+/// Kind of the encrypted requests and responses between the app and a remote signer.
+pub const NIP46_KIND: u32 = 24_133;
+
+/// How a remote signer is reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignerUri {
+    /// `bunker://` URI given by the signer, the app connects to it.
+    Bunker {
+        remote_pubkey: String,
+        relays: Vec<String>,
+        secret: Option<String>,
+    },
+    /// `nostrconnect://` URI shown by the app, the signer connects to it and
+    /// answers with the secret.
+    NostrConnect {
+        client_pubkey: String,
+        relays: Vec<String>,
+        secret: String,
+        name: Option<String>,
+    },
+}
+
+impl SignerUri {
+    /// Relays requests and responses go through.
+    #[must_use]
+    pub fn relays(&self) -> &[String] {
+        match self {
+            Self::Bunker { relays, .. } | Self::NostrConnect { relays, .. } => relays,
+        }
+    }
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = s.as_bytes();
+    while let [byte, tail @ ..] = rest {
+        if *byte == b'%' {
+            let code = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(code, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(*byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+impl FromStr for SignerUri {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (scheme, rest) = s.trim().split_once("://").ok_or(())?;
+        let (pubkey, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let pubkey = pubkey.trim_end_matches('/').to_lowercase();
+        if !is_hex_pubkey(&pubkey) {
+            return Err(());
+        }
+        let (mut relays, mut secret, mut name) = (vec![], None, None);
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode(value).ok_or(())?;
+            match key {
+                "relay" => relays.push(value),
+                "secret" => secret = Some(value),
+                "name" => name = Some(value),
+                _ => {}
+            }
+        }
+        // Without a relay there's no way to reach the other side
+        if relays.is_empty() {
+            return Err(());
+        }
+        match scheme.to_lowercase().as_str() {
+            "bunker" => Ok(Self::Bunker {
+                remote_pubkey: pubkey,
+                relays,
+                secret,
+            }),
+            "nostrconnect" => Ok(Self::NostrConnect {
+                client_pubkey: pubkey,
+                relays,
+                secret: secret.ok_or(())?,
+                name,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::fmt::Display for SignerUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (scheme, pubkey, secret, name) = match self {
+            Self::Bunker {
+                remote_pubkey,
+                secret,
+                ..
+            } => ("bunker", remote_pubkey, secret.as_ref(), None),
+            Self::NostrConnect {
+                client_pubkey,
+                secret,
+                name,
+                ..
+            } => ("nostrconnect", client_pubkey, Some(secret), name.as_ref()),
+        };
+        let query = self
+            .relays()
+            .iter()
+            .map(|relay| format!("relay={}", percent_encode(relay)))
+            .chain(secret.map(|secret| format!("secret={}", percent_encode(secret))))
+            .chain(name.map(|name| format!("name={}", percent_encode(name))))
+            .collect::<Vec<_>>()
+            .join("&");
+        write!(f, "{scheme}://{pubkey}?{query}")
+    }
+}
+
+/// Request sent to the signer, encrypted in a `NIP46_KIND` note.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Nip46Request {
+    pub id: String,
+    pub method: String,
+    pub params: Vec<String>,
+}
+
+impl std::fmt::Display for Nip46Request {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{json}")
+    }
+}
+
+/// Signer's answer to the request with the same `id`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Nip46Response {
+    pub id: String,
+    #[serde(default)]
+    pub result: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl Nip46Response {
+    /// URL the user has to open to approve the request, the signer answers
+    /// again once they have.
+    #[must_use]
+    pub fn auth_url(&self) -> Option<&str> {
+        (self.result.as_deref() == Some("auth_url"))
+            .then_some(self.error.as_deref())
+            .flatten()
+    }
+}
+
+impl FromStr for Nip46Response {
+    type Err = serde_json::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBKEY: &str = "7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e";
+
+    #[test]
+    fn reads_bunker_uris() {
+        let uri = format!(
+            "bunker://{PUBKEY}?relay=wss%3A%2F%2Frelay.nsec.app&relay=wss://nos.lol&secret=abc"
+        );
+        assert_eq!(
+            uri.parse(),
+            Ok(SignerUri::Bunker {
+                remote_pubkey: PUBKEY.to_string(),
+                relays: vec![
+                    "wss://relay.nsec.app".to_string(),
+                    "wss://nos.lol".to_string()
+                ],
+                secret: Some("abc".to_string()),
+            })
+        );
+        assert_eq!(format!("bunker://{PUBKEY}").parse::<SignerUri>(), Err(()));
+        assert_eq!(
+            "bunker://npub1xyz?relay=wss://nos.lol".parse::<SignerUri>(),
+            Err(())
+        );
+    }
+
+    #[test]
+    fn nostrconnect_uris_round_trip() {
+        let uri = SignerUri::NostrConnect {
+            client_pubkey: PUBKEY.to_string(),
+            relays: vec!["wss://relay.nsec.app".to_string()],
+            secret: "0f1e2d".to_string(),
+            name: Some("Rooky Annotator".to_string()),
+        };
+        let encoded = uri.to_string();
+        assert!(encoded.starts_with(&format!("nostrconnect://{PUBKEY}?relay=wss%3A%2F%2F")));
+        assert_eq!(encoded.parse(), Ok(uri));
+    }
+
+    #[test]
+    fn reads_auth_challenges() {
+        let response = r#"{"id":"1","result":"auth_url","error":"https://signer.example/auth"}"#
+            .parse::<Nip46Response>()
+            .unwrap();
+        assert_eq!(response.auth_url(), Some("https://signer.example/auth"));
+        let response = r#"{"id":"1","result":"ack"}"#.parse::<Nip46Response>().unwrap();
+        assert_eq!(response.auth_url(), None);
+    }
+}
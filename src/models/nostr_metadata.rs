@@ -1,4 +1,3 @@
-use nostr_minions::key_manager::UserIdentity;
 use nostr_minions::nostro2::NostrNote;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -76,10 +75,10 @@ fn exclude_note(_: &NostrNote) -> bool {
 
 impl UserMetadataIdb {
     /// # Panics
-    /// Panics if the `UserIdentity` doesn't provide a public key or if signing fails.
+    /// Panics if the signer refuses to sign the metadata.
     #[allow(clippy::future_not_send)]
-    pub async fn new(metadata: NostrMetadata, keys: &UserIdentity) -> Self {
-        let pubkey = keys.get_pubkey().await.expect("no pubkey");
+    pub async fn new(metadata: NostrMetadata, signer: &super::Signer) -> Self {
+        let pubkey = signer.pubkey().to_string();
         let created_at = chrono::Utc::now().timestamp();
         let mut note = NostrNote {
             pubkey: pubkey.clone(),
@@ -95,7 +94,8 @@ impl UserMetadataIdb {
         }
 
         let mut note_to_sign = note.clone();
-        keys.sign_nostr_note(&mut note_to_sign)
+        signer
+            .sign_note(&mut note_to_sign)
             .await
             .expect("Failed to sign nostr note");
 
@@ -168,3 +168,24 @@ impl TryFrom<NostrNote> for UserMetadataIdb {
         })
     }
 }
+
+/// First values of the tags of a note named `name`, like the pubkeys of its `p` tags.
+#[must_use]
+pub fn tag_values(note: &NostrNote, name: &str) -> Vec<String> {
+    // Tags are read in their NIP-01 form, `[<name>, <value>, ...]`
+    #[derive(Deserialize)]
+    struct Tagged {
+        tags: Vec<Vec<String>>,
+    }
+    serde_json::to_value(note)
+        .and_then(serde_json::from_value::<Tagged>)
+        .map(|tagged| {
+            tagged
+                .tags
+                .into_iter()
+                .filter(|tag| tag.first().is_some_and(|tag_name| tag_name == name))
+                .filter_map(|tag| tag.into_iter().nth(1))
+                .collect()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,113 @@
+use crate::configs::Language;
+
+/// How SAN moves are shown to the user.
+///
+/// Moves are always stored and exported in English SAN, this only changes the
+/// piece letters on screen: localized letters, or figurines when enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SanNotation {
+    pub language: Language,
+    pub figurine: bool,
+}
+
+impl SanNotation {
+    /// Pieces shown on the keypad, in the order of the English `N B R Q K`.
+    pub const PIECES: [shakmaty::Role; 5] = [
+        shakmaty::Role::Knight,
+        shakmaty::Role::Bishop,
+        shakmaty::Role::Rook,
+        shakmaty::Role::Queen,
+        shakmaty::Role::King,
+    ];
+
+    #[must_use]
+    pub const fn new(language: Language, figurine: bool) -> Self {
+        Self { language, figurine }
+    }
+    /// Letter of `role` in the scoresheets of the language.
+    #[must_use]
+    pub const fn letter(self, role: shakmaty::Role) -> char {
+        match (self.language, role) {
+            (_, shakmaty::Role::Pawn) => 'P',
+            (Language::English, shakmaty::Role::Knight) => 'N',
+            (Language::English, shakmaty::Role::Bishop) => 'B',
+            (Language::English, shakmaty::Role::Rook) => 'R',
+            (Language::English, shakmaty::Role::Queen) => 'Q',
+            (Language::English, shakmaty::Role::King) => 'K',
+            (Language::Spanish | Language::Portuguese, shakmaty::Role::Knight) => 'C',
+            (Language::Spanish, shakmaty::Role::Bishop) => 'A',
+            (Language::Portuguese, shakmaty::Role::Bishop) => 'B',
+            (Language::Spanish | Language::Portuguese, shakmaty::Role::Rook) => 'T',
+            (Language::Spanish | Language::Portuguese, shakmaty::Role::Queen) => 'D',
+            (Language::Spanish | Language::Portuguese, shakmaty::Role::King) => 'R',
+        }
+    }
+    /// Symbol written for `role`, the figurine or the localized letter.
+    #[must_use]
+    pub const fn symbol(self, role: shakmaty::Role) -> char {
+        if !self.figurine {
+            return self.letter(role);
+        }
+        match role {
+            shakmaty::Role::Pawn => '♙',
+            shakmaty::Role::Knight => '♘',
+            shakmaty::Role::Bishop => '♗',
+            shakmaty::Role::Rook => '♖',
+            shakmaty::Role::Queen => '♕',
+            shakmaty::Role::King => '♔',
+        }
+    }
+    /// Piece written with the localized `letter`.
+    #[must_use]
+    pub fn role_from_letter(self, letter: char) -> Option<shakmaty::Role> {
+        Self::PIECES
+            .into_iter()
+            .find(|role| self.letter(*role) == letter)
+    }
+    /// Writes `san` with the symbols of this notation.
+    #[must_use]
+    pub fn display(self, san: &shakmaty::san::SanPlus) -> String {
+        let mut text = String::new();
+        match &san.san {
+            shakmaty::san::San::Normal {
+                role,
+                file,
+                rank,
+                capture,
+                to,
+                promotion,
+            } => {
+                if *role != shakmaty::Role::Pawn {
+                    text.push(self.symbol(*role));
+                }
+                if let Some(file) = file {
+                    text.push(file.char());
+                }
+                if let Some(rank) = rank {
+                    text.push(rank.char());
+                }
+                if *capture {
+                    text.push('x');
+                }
+                text.push_str(&to.to_string());
+                if let Some(promotion) = promotion {
+                    text.push('=');
+                    text.push(self.symbol(*promotion));
+                }
+            }
+            shakmaty::san::San::Put { role, to } => {
+                if *role != shakmaty::Role::Pawn {
+                    text.push(self.symbol(*role));
+                }
+                text.push('@');
+                text.push_str(&to.to_string());
+            }
+            // Castling and null moves have no piece letters
+            other => text.push_str(&other.to_string()),
+        }
+        if let Some(suffix) = &san.suffix {
+            text.push_str(&suffix.to_string());
+        }
+        text
+    }
+}
@@ -0,0 +1,234 @@
+use super::{GameTree, MoveId};
+use shakmaty::Position;
+
+/// A game read from PGN text, with its headers and the full move tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PgnGame {
+    pub headers: Vec<(String, String)>,
+    pub tree: GameTree,
+}
+
+impl PgnGame {
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The first move of a game that isn't legal in its position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IllegalMove {
+    /// Half moves from the start of the game up to the illegal one, which is included.
+    /// 0 when the `FEN` header itself is invalid, `san` then holds the FEN.
+    pub ply: usize,
+    /// The illegal move as written in the text, with its move number.
+    pub san: String,
+    /// Everything read before the illegal move.
+    pub partial: PgnGame,
+}
+
+impl std::fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ply == 0 {
+            write!(f, "Invalid starting position {}", self.san)
+        } else {
+            write!(f, "Illegal move {} at ply {}", self.san, self.ply)
+        }
+    }
+}
+
+/// Reads the first game of `pgn`, including variations, comments and NAGs.
+/// Returns `None` if there is no game or one of its moves is illegal.
+#[must_use]
+pub fn parse_pgn(pgn: &str) -> Option<PgnGame> {
+    read_pgn_games(pgn).into_iter().next()?.ok()
+}
+
+/// Reads every game of `pgn`, each one stopping at its first illegal move.
+#[must_use]
+pub fn read_pgn_games(pgn: &str) -> Vec<Result<PgnGame, IllegalMove>> {
+    let mut reader = pgn_reader::BufferedReader::new_cursor(pgn.as_bytes());
+    let mut visitor = TreeBuilder::default();
+    let mut games = vec![];
+    while let Ok(Some(game)) = reader.read_game(&mut visitor) {
+        games.push(game);
+    }
+    games
+}
+
+#[derive(Default)]
+struct TreeBuilder {
+    game: PgnGame,
+    current: MoveId,
+    /// Moves to return to when the variations being read end.
+    variation_stack: Vec<MoveId>,
+    /// Illegal move and its ply, the rest of the game is skipped once set.
+    illegal: Option<(usize, String)>,
+}
+
+impl pgn_reader::Visitor for TreeBuilder {
+    type Result = Result<PgnGame, IllegalMove>;
+
+    fn begin_game(&mut self) {
+        *self = Self::default();
+    }
+    fn header(&mut self, key: &[u8], value: pgn_reader::RawHeader<'_>) {
+        self.game.headers.push((
+            String::from_utf8_lossy(key).to_string(),
+            value.decode_utf8_lossy().to_string(),
+        ));
+    }
+    fn end_headers(&mut self) -> pgn_reader::Skip {
+        // Unknown variants are read as standard chess, their moves will show up as illegal
+        let variant = self
+            .game
+            .header("Variant")
+            .and_then(super::GameVariant::from_pgn_name)
+            .unwrap_or_default();
+        let Some(fen) = self.game.header("FEN").map(ToString::to_string) else {
+            self.game.tree = GameTree::new(variant.start_position());
+            return pgn_reader::Skip(false);
+        };
+        match super::position_from_fen(&fen, variant) {
+            Ok(start) => {
+                self.game.tree = GameTree::new(start);
+                pgn_reader::Skip(false)
+            }
+            Err(_) => {
+                self.illegal = Some((0, fen));
+                pgn_reader::Skip(true)
+            }
+        }
+    }
+    fn san(&mut self, san_plus: pgn_reader::SanPlus) {
+        if self.illegal.is_some() {
+            return;
+        }
+        let position = self.game.tree.position(self.current);
+        let next = san_plus
+            .san
+            .to_move(&position)
+            .ok()
+            .and_then(|mv| self.game.tree.play(self.current, &mv));
+        match next {
+            Some(next) => self.current = next,
+            None => {
+                let ply = self.game.tree.path_to(self.current).len() + 1;
+                let number = match position.turn() {
+                    shakmaty::Color::White => format!("{}.", position.fullmoves()),
+                    shakmaty::Color::Black => format!("{}...", position.fullmoves()),
+                };
+                self.illegal = Some((ply, format!("{number} {san_plus}")));
+            }
+        }
+    }
+    fn nag(&mut self, nag: pgn_reader::Nag) {
+        if self.illegal.is_some() {
+            return;
+        }
+        let is_set = self
+            .game
+            .tree
+            .get(self.current)
+            .is_some_and(|node| node.nags.contains(&nag.0));
+        if !is_set {
+            self.game.tree.toggle_nag(self.current, nag.0);
+        }
+    }
+    fn comment(&mut self, comment: pgn_reader::RawComment<'_>) {
+        if self.illegal.is_some() {
+            return;
+        }
+        let comment = String::from_utf8_lossy(comment.as_bytes());
+        let (clock, comment) = super::take_clock_command(&comment);
+        if clock.is_some() {
+            self.game.tree.set_clock(self.current, clock);
+        }
+        if comment.is_empty() {
+            return;
+        }
+        // Consecutive comments on the same move are joined
+        let joined = match self
+            .game
+            .tree
+            .get(self.current)
+            .and_then(|node| node.comment.as_deref())
+        {
+            Some(existing) => format!("{existing} {}", comment.trim()),
+            None => comment.trim().to_string(),
+        };
+        self.game.tree.set_comment(self.current, &joined);
+    }
+    fn begin_variation(&mut self) -> pgn_reader::Skip {
+        if self.illegal.is_some() {
+            return pgn_reader::Skip(true);
+        }
+        // A variation is an alternative to the last move, so it starts from its parent
+        self.variation_stack.push(self.current);
+        self.current = self
+            .game
+            .tree
+            .parent(self.current)
+            .unwrap_or(GameTree::ROOT);
+        pgn_reader::Skip(false)
+    }
+    fn end_variation(&mut self) {
+        if let Some(current) = self.variation_stack.pop() {
+            self.current = current;
+        }
+    }
+    fn end_game(&mut self) -> Self::Result {
+        let game = std::mem::take(&mut self.game);
+        match self.illegal.take() {
+            Some((ply, san)) => Err(IllegalMove {
+                ply,
+                san,
+                partial: game,
+            }),
+            None => Ok(game),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_variations_comments_and_nags() {
+        let game = parse_pgn(
+            "[White \"Tal\"]\n[Black \"Botvinnik\"]\n\n\
+             {Opening} 1. e4 $1 $1 {best} e5 (1... c5 2. Nf3 (2. Nc3)) 2. Nf3 *",
+        )
+        .unwrap();
+        assert_eq!(game.header("White"), Some("Tal"));
+        assert_eq!(game.header("Event"), None);
+        assert_eq!(
+            game.tree.to_movetext(),
+            "{Opening} 1. e4 $1 {best} 1... e5 (1... c5 2. Nf3 (2. Nc3)) 2. Nf3"
+        );
+    }
+
+    #[test]
+    fn stops_at_the_first_illegal_move() {
+        let games = read_pgn_games("1. e4 e5 2. Ke3 Nc6 *\n\n1. d4 *");
+        assert_eq!(games.len(), 2);
+        let illegal = games[0].as_ref().unwrap_err();
+        assert_eq!(illegal.ply, 3);
+        assert_eq!(illegal.san, "2. Ke3");
+        assert_eq!(illegal.partial.tree.to_movetext(), "1. e4 e5");
+        assert!(games[1].is_ok());
+        assert_eq!(parse_pgn("1. e4 e5 2. Ke3 *"), None);
+    }
+
+    #[test]
+    fn rejects_an_invalid_starting_position() {
+        let games = read_pgn_games("[SetUp \"1\"]\n[FEN \"not a fen\"]\n\n1. e4 *");
+        let illegal = games[0].as_ref().unwrap_err();
+        assert_eq!(illegal.ply, 0);
+        assert_eq!(illegal.san, "not a fen");
+    }
+}
@@ -0,0 +1,315 @@
+use std::str::FromStr;
+
+use super::nip19::{bech32_decode, hex, tlv_entries};
+
+/// Why a recipient could not be turned into a pubkey.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecipientError {
+    Empty,
+    /// Not an npub, hex pubkey, nprofile or NIP-05 identifier.
+    Unrecognized,
+    /// The domain doesn't list the name in its `nostr.json`.
+    UnknownName,
+    /// The `nostr.json` of the domain could not be fetched or read.
+    Lookup(String),
+}
+
+impl std::fmt::Display for RecipientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "No recipient given"),
+            Self::Unrecognized => write!(f, "Not a nostr public key or NIP-05 identifier"),
+            Self::UnknownName => write!(f, "Name not found on the NIP-05 domain"),
+            Self::Lookup(reason) => write!(f, "NIP-05 lookup failed: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for RecipientError {}
+
+/// Recipient as typed by the user.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecipientId {
+    /// Hex pubkey, already decoded from npub or nprofile.
+    Pubkey(String),
+    /// NIP-05 identifier, a bare domain stands for its `_` name.
+    Nip05 { name: String, domain: String },
+}
+
+/// `nprofile` TLV entry holding the pubkey, the others are relay hints.
+const NPROFILE_PUBKEY_TLV: u8 = 0;
+
+impl FromStr for RecipientId {
+    type Err = RecipientError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+        let input = input.strip_prefix("nostr:").unwrap_or(input);
+        if input.is_empty() {
+            return Err(RecipientError::Empty);
+        }
+        if is_hex_pubkey(input) {
+            return Ok(Self::Pubkey(input.to_lowercase()));
+        }
+        if let Some((hrp, data)) = bech32_decode(input) {
+            return match hrp.as_str() {
+                "npub" if data.len() == 32 => Ok(Self::Pubkey(hex(&data))),
+                "nprofile" => tlv_entries(&data)
+                    .and_then(|entries| {
+                        entries
+                            .into_iter()
+                            .find(|(kind, value)| *kind == NPROFILE_PUBKEY_TLV && value.len() == 32)
+                    })
+                    .map(|(_, pubkey)| Self::Pubkey(hex(pubkey)))
+                    .ok_or(RecipientError::Unrecognized),
+                _ => Err(RecipientError::Unrecognized),
+            };
+        }
+        let (name, domain) = input.rsplit_once('@').unwrap_or(("_", input));
+        let valid_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if valid_name && domain.contains('.') && !domain.contains(['/', ' ', '@']) {
+            return Ok(Self::Nip05 {
+                name: name.to_lowercase(),
+                domain: domain.to_lowercase(),
+            });
+        }
+        Err(RecipientError::Unrecognized)
+    }
+}
+
+pub(super) fn is_hex_pubkey(s: &str) -> bool {
+    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Pubkey listed for `name` in the body of a NIP-05 `nostr.json`.
+///
+/// # Errors
+/// Returns `RecipientError::Lookup` if the body isn't a `nostr.json` document
+/// and `RecipientError::UnknownName` if the name isn't listed in it.
+pub fn pubkey_from_nostr_json(body: &str, name: &str) -> Result<String, RecipientError> {
+    #[derive(serde::Deserialize)]
+    struct NostrJson {
+        names: std::collections::HashMap<String, String>,
+    }
+    let json: NostrJson =
+        serde_json::from_str(body).map_err(|e| RecipientError::Lookup(e.to_string()))?;
+    json.names
+        .iter()
+        .find(|(listed, _)| listed.to_lowercase() == name)
+        .map(|(_, pubkey)| pubkey.to_lowercase())
+        .filter(|pubkey| is_hex_pubkey(pubkey))
+        .ok_or(RecipientError::UnknownName)
+}
+
+/// Source of NIP-05 `nostr.json` documents, swapped for a fixed answer in tests.
+#[allow(async_fn_in_trait)]
+pub trait Nip05Lookup {
+    /// Body of `https://<domain>/.well-known/nostr.json?name=<name>`.
+    ///
+    /// # Errors
+    /// Returns `RecipientError::Lookup` if the document can't be fetched.
+    async fn nostr_json(&self, name: &str, domain: &str) -> Result<String, RecipientError>;
+}
+
+/// Fetches `nostr.json` from the domain of the identifier.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WellKnownLookup;
+
+impl Nip05Lookup for WellKnownLookup {
+    async fn nostr_json(&self, name: &str, domain: &str) -> Result<String, RecipientError> {
+        let url = format!("https://{domain}/.well-known/nostr.json?name={name}");
+        let response = gloo::net::http::Request::get(&url)
+            .send()
+            .await
+            .map_err(|e| RecipientError::Lookup(e.to_string()))?;
+        if !response.ok() {
+            return Err(RecipientError::Lookup(response.status_text()));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| RecipientError::Lookup(e.to_string()))
+    }
+}
+
+/// Hex pubkey of a recipient typed as npub, hex, nprofile or NIP-05 identifier.
+///
+/// # Errors
+/// Returns the reason the input is not a recipient, or why its NIP-05 lookup failed.
+pub async fn resolve_recipient(
+    input: &str,
+    lookup: &impl Nip05Lookup,
+) -> Result<String, RecipientError> {
+    match input.parse::<RecipientId>()? {
+        RecipientId::Pubkey(pubkey) => Ok(pubkey),
+        RecipientId::Nip05 { name, domain } => {
+            let body = lookup.nostr_json(&name, &domain).await?;
+            pubkey_from_nostr_json(&body, &name)
+        }
+    }
+}
+
+/// Pubkeys followed in a kind 3 contact list, in the order they were added.
+#[must_use]
+pub fn followed_pubkeys(contact_list: &nostr_minions::nostro2::NostrNote) -> Vec<String> {
+    super::tag_values(contact_list, "p")
+        .into_iter()
+        .map(|pubkey| pubkey.to_lowercase())
+        .filter(|pubkey| is_hex_pubkey(pubkey))
+        .collect()
+}
+
+/// Pubkeys games were last sent to, offered first when picking a recipient.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RecentRecipientsEntry {
+    id: String,
+    pub pubkeys: Vec<String>,
+}
+
+impl Default for RecentRecipientsEntry {
+    fn default() -> Self {
+        Self {
+            id: Self::ID.to_string(),
+            pubkeys: vec![],
+        }
+    }
+}
+
+impl RecentRecipientsEntry {
+    pub const ID: &'static str = "recent_recipients";
+    /// Recipients remembered, older ones are forgotten.
+    const LIMIT: usize = 10;
+
+    /// The list with `pubkey` moved to the front.
+    #[must_use]
+    pub fn with_recipient(mut self, pubkey: &str) -> Self {
+        self.pubkeys.retain(|known| known != pubkey);
+        self.pubkeys.insert(0, pubkey.to_string());
+        self.pubkeys.truncate(Self::LIMIT);
+        self
+    }
+}
+
+impl TryFrom<web_sys::wasm_bindgen::JsValue> for RecentRecipientsEntry {
+    type Error = web_sys::wasm_bindgen::JsValue;
+    fn try_from(value: web_sys::wasm_bindgen::JsValue) -> Result<Self, Self::Error> {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+}
+impl From<RecentRecipientsEntry> for web_sys::wasm_bindgen::JsValue {
+    fn from(value: RecentRecipientsEntry) -> Self {
+        serde_wasm_bindgen::to_value(&value).unwrap_or_default()
+    }
+}
+
+impl nostr_minions::browser_api::IdbStoreManager for RecentRecipientsEntry {
+    fn config() -> nostr_minions::browser_api::IdbStoreConfig {
+        nostr_minions::browser_api::IdbStoreConfig {
+            db_name: "annotator_recipients_db",
+            store_name: "annotator_recipients_store",
+            db_version: 1,
+            document_key: "id",
+        }
+    }
+    fn key(&self) -> web_sys::wasm_bindgen::JsValue {
+        web_sys::wasm_bindgen::JsValue::from_str(&self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBKEY: &str = "7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e";
+
+    /// Answers every lookup with the same document.
+    struct FixedLookup(Result<&'static str, RecipientError>);
+
+    impl Nip05Lookup for FixedLookup {
+        async fn nostr_json(&self, _name: &str, _domain: &str) -> Result<String, RecipientError> {
+            self.0.clone().map(ToString::to_string)
+        }
+    }
+
+    /// Runs a future that never waits, which is all the fixed lookup does.
+    fn resolve(input: &str, lookup: &FixedLookup) -> Result<String, RecipientError> {
+        let future = std::pin::pin!(resolve_recipient(input, lookup));
+        match std::future::Future::poll(
+            future,
+            &mut std::task::Context::from_waker(std::task::Waker::noop()),
+        ) {
+            std::task::Poll::Ready(result) => result,
+            std::task::Poll::Pending => panic!("lookup should be immediate"),
+        }
+    }
+
+    #[test]
+    fn parses_pubkey_encodings() {
+        assert_eq!(
+            "npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptg"
+                .parse::<RecipientId>(),
+            Ok(RecipientId::Pubkey(PUBKEY.to_string()))
+        );
+        assert_eq!(
+            format!("nostr:{}", PUBKEY.to_uppercase()).parse::<RecipientId>(),
+            Ok(RecipientId::Pubkey(PUBKEY.to_string()))
+        );
+        assert_eq!(
+            "nprofile1qqsrhuxx8l9ex335q7he0f09aej04zpazpl0ne2cgukyawd24mayt8gpp4mhxue69uhhytnc9e3k7mgpz4mhxue69uhkg6nzv9ejuumpv34kytnrdaksjlyr9p"
+                .parse::<RecipientId>(),
+            Ok(RecipientId::Pubkey(
+                "3bf0c63fcb93463407af97a5e5ee64fa883d107ef9e558472c4eb9aaaefa459d".to_string()
+            ))
+        );
+        // One character off breaks the checksum
+        assert_eq!(
+            "npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptq"
+                .parse::<RecipientId>(),
+            Err(RecipientError::Unrecognized)
+        );
+        assert_eq!("  ".parse::<RecipientId>(), Err(RecipientError::Empty));
+    }
+
+    #[test]
+    fn parses_nip05_identifiers() {
+        assert_eq!(
+            "Bob@Example.com".parse::<RecipientId>(),
+            Ok(RecipientId::Nip05 {
+                name: "bob".to_string(),
+                domain: "example.com".to_string()
+            })
+        );
+        assert_eq!(
+            "example.com".parse::<RecipientId>(),
+            Ok(RecipientId::Nip05 {
+                name: "_".to_string(),
+                domain: "example.com".to_string()
+            })
+        );
+        assert_eq!(
+            "bob@localhost".parse::<RecipientId>(),
+            Err(RecipientError::Unrecognized)
+        );
+    }
+
+    #[test]
+    fn resolves_nip05_through_the_lookup() {
+        let found = FixedLookup(Ok(
+            r#"{"names":{"bob":"7E7E9C42A91BFEF19FA929E5FDA1B72E0EBC1A4C1141673E2794234D86ADDF4E"}}"#,
+        ));
+        assert_eq!(resolve("bob@example.com", &found), Ok(PUBKEY.to_string()));
+        assert_eq!(
+            resolve("alice@example.com", &found),
+            Err(RecipientError::UnknownName)
+        );
+        let offline = FixedLookup(Err(RecipientError::Lookup("offline".to_string())));
+        assert_eq!(
+            resolve("bob@example.com", &offline),
+            Err(RecipientError::Lookup("offline".to_string()))
+        );
+        // Keys never reach the lookup
+        assert_eq!(resolve(PUBKEY, &offline), Ok(PUBKEY.to_string()));
+    }
+}
@@ -0,0 +1,630 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::nip46::{Nip46Request, Nip46Response, SignerUri, NIP46_KIND};
+use nostr_minions::key_manager::UserIdentity;
+use nostr_minions::nostro2::NostrNote;
+use nostr_minions::nostro2_signer::keypair::NostrKeypair;
+use nostr_minions::nostro2_signer::nostro2_nips::{Nip17, Nip44, Nip59};
+use wasm_bindgen::prelude::*;
+use yew::platform::pinned::oneshot;
+
+/// NIP-59 seal, signed by the sender and holding the encrypted rumor.
+const SEAL_KIND: u32 = 13;
+/// NIP-59 gift wrap, the outer note of every NIP-17 direct message.
+pub const GIFT_WRAP_KIND: u32 = 1059;
+/// NIP-17 chat message, the rumor sealed inside the gift wrap.
+pub const CHAT_MESSAGE_KIND: u32 = 14;
+/// Seals and gift wraps are dated up to two days back, so relays can't tell
+/// when the message was sent.
+const TIMESTAMP_TWEAK_SECS: f64 = 2.0 * 24.0 * 60.0 * 60.0;
+/// How long a remote signer has to answer a request, approval included.
+const REMOTE_REQUEST_TIMEOUT_MS: u32 = 60_000;
+
+/// Why a note couldn't be signed, encrypted or opened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignerError {
+    /// No NIP-07 extension in this browser.
+    NoExtension,
+    /// The signer refused or failed, with its message.
+    Rejected(String),
+    /// A note or message that couldn't be read.
+    Malformed,
+}
+
+impl std::fmt::Display for SignerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoExtension => write!(f, "no nostr extension found"),
+            Self::Rejected(reason) => write!(f, "signer refused: {reason}"),
+            Self::Malformed => write!(f, "malformed note or message"),
+        }
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+fn rejected(error: impl std::fmt::Debug) -> SignerError {
+    SignerError::Rejected(format!("{error:?}"))
+}
+
+/// A remote signer that let `REMOTE_REQUEST_TIMEOUT_MS` go by.
+fn no_answer() -> SignerError {
+    SignerError::Rejected("no answer from the signer".to_string())
+}
+
+fn js_rejected(error: JsValue) -> SignerError {
+    error
+        .as_string()
+        .map_or_else(|| rejected(error), SignerError::Rejected)
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// `window.nostr`, injected by NIP-07 extensions.
+    type WindowNostr;
+    #[wasm_bindgen(method, catch, js_name = getPublicKey)]
+    async fn get_public_key(this: &WindowNostr) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(method, catch, js_name = signEvent)]
+    async fn sign_event(this: &WindowNostr, event: JsValue) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(method, getter)]
+    fn nip44(this: &WindowNostr) -> Option<WindowNip44>;
+
+    type WindowNip44;
+    #[wasm_bindgen(method, catch)]
+    async fn encrypt(this: &WindowNip44, pubkey: &str, plaintext: &str)
+        -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(method, catch)]
+    async fn decrypt(
+        this: &WindowNip44,
+        pubkey: &str,
+        ciphertext: &str,
+    ) -> Result<JsValue, JsValue>;
+}
+
+fn window_nostr() -> Option<WindowNostr> {
+    let window = web_sys::window()?;
+    let nostr = web_sys::js_sys::Reflect::get(&window, &"nostr".into()).ok()?;
+    (!nostr.is_undefined() && !nostr.is_null()).then(|| nostr.unchecked_into())
+}
+
+fn extension_nip44() -> Result<WindowNip44, SignerError> {
+    window_nostr()
+        .ok_or(SignerError::NoExtension)?
+        .nip44()
+        .ok_or_else(|| SignerError::Rejected("the extension doesn't support NIP-44".to_string()))
+}
+
+/// Whether a NIP-07 extension is there to log in with.
+#[must_use]
+pub fn has_extension() -> bool {
+    window_nostr().is_some()
+}
+
+/// Pubkey of the user logged in to the NIP-07 extension.
+///
+/// # Errors
+/// Returns `SignerError::NoExtension` without an extension, and
+/// `SignerError::Rejected` if the user doesn't allow it.
+pub async fn extension_pubkey() -> Result<String, SignerError> {
+    let nostr = window_nostr().ok_or(SignerError::NoExtension)?;
+    nostr
+        .get_public_key()
+        .await
+        .map_err(js_rejected)?
+        .as_string()
+        .ok_or(SignerError::Malformed)
+}
+
+/// Short random hex string, for request ids and connection secrets.
+fn random_token() -> String {
+    NostrKeypair::generate(false)
+        .public_key()
+        .chars()
+        .take(16)
+        .collect()
+}
+
+/// Session with a NIP-46 remote signer. Requests go out as notes through the
+/// relay pool, and the responses are handed back to `receive`.
+#[derive(Clone)]
+pub struct RemoteSigner {
+    /// Key of this app, the only one the signer answers to.
+    client: NostrKeypair,
+    /// Signer's pubkey, unknown for a `nostrconnect://` URI until it answers.
+    remote_pubkey: Rc<RefCell<Option<String>>>,
+    relays: Vec<String>,
+    /// Secret of the `bunker://` URI, or the one the signer answers a
+    /// `nostrconnect://` URI with.
+    secret: Option<String>,
+    outbox: yew::Callback<NostrNote>,
+    pending: Rc<RefCell<HashMap<String, oneshot::Sender<Nip46Response>>>>,
+    /// Taken by `connect` for a `nostrconnect://` URI, resolved when the signer answers.
+    answered: Rc<RefCell<Option<oneshot::Receiver<()>>>>,
+    on_answer: Rc<RefCell<Option<oneshot::Sender<()>>>>,
+}
+
+impl PartialEq for RemoteSigner {
+    fn eq(&self, other: &Self) -> bool {
+        self.client.public_key() == other.client.public_key()
+    }
+}
+
+impl RemoteSigner {
+    fn new(
+        client: NostrKeypair,
+        remote_pubkey: Option<String>,
+        relays: Vec<String>,
+        secret: Option<String>,
+        outbox: yew::Callback<NostrNote>,
+    ) -> Self {
+        Self {
+            client,
+            remote_pubkey: Rc::new(RefCell::new(remote_pubkey)),
+            relays,
+            secret,
+            outbox,
+            pending: Rc::default(),
+            answered: Rc::default(),
+            on_answer: Rc::default(),
+        }
+    }
+    /// Session for a `bunker://` URI, `None` for a `nostrconnect://` one.
+    #[must_use]
+    pub fn bunker(uri: &SignerUri, outbox: yew::Callback<NostrNote>) -> Option<Self> {
+        let SignerUri::Bunker {
+            remote_pubkey,
+            relays,
+            secret,
+        } = uri
+        else {
+            return None;
+        };
+        Some(Self::new(
+            NostrKeypair::generate(true),
+            Some(remote_pubkey.clone()),
+            relays.clone(),
+            secret.clone(),
+            outbox,
+        ))
+    }
+    /// Session waiting for a signer to scan or paste the `nostrconnect://` URI
+    /// returned with it.
+    #[must_use]
+    pub fn nostr_connect(
+        relays: Vec<String>,
+        outbox: yew::Callback<NostrNote>,
+    ) -> (Self, SignerUri) {
+        let session = Self::new(
+            NostrKeypair::generate(true),
+            None,
+            relays.clone(),
+            Some(random_token()),
+            outbox,
+        );
+        let (on_answer, answered) = oneshot::channel();
+        *session.on_answer.borrow_mut() = Some(on_answer);
+        *session.answered.borrow_mut() = Some(answered);
+        let uri = SignerUri::NostrConnect {
+            client_pubkey: session.client_pubkey(),
+            relays,
+            secret: session.secret.clone().unwrap_or_default(),
+            name: Some("Rooky".to_string()),
+        };
+        (session, uri)
+    }
+    /// Session of an earlier login, already connected.
+    #[must_use]
+    pub fn restore(login: &RemoteLogin, outbox: yew::Callback<NostrNote>) -> Option<Self> {
+        let mut client = login.client_nsec.parse::<NostrKeypair>().ok()?;
+        client.set_extractable(true);
+        Some(Self::new(
+            client,
+            Some(login.remote_pubkey.clone()),
+            login.relays.clone(),
+            None,
+            outbox,
+        ))
+    }
+    #[must_use]
+    pub fn client_pubkey(&self) -> String {
+        self.client.public_key()
+    }
+    #[must_use]
+    pub fn relays(&self) -> &[String] {
+        &self.relays
+    }
+    /// What to store to log in again with this session.
+    #[must_use]
+    pub fn login(&self, user_pubkey: &str) -> Option<RemoteLogin> {
+        Some(RemoteLogin {
+            user_pubkey: user_pubkey.to_string(),
+            remote_pubkey: self.remote_pubkey.borrow().clone()?,
+            relays: self.relays.clone(),
+            client_nsec: self.client.nsec().ok()?,
+        })
+    }
+
+    /// Hands a note from the relays to the request it answers.
+    pub fn receive(&self, note: &NostrNote) {
+        if note.kind != NIP46_KIND || !super::tag_values(note, "p").contains(&self.client_pubkey())
+        {
+            return;
+        }
+        let Some(response) = self
+            .client
+            .nip_44_decrypt(&note.content, &note.pubkey)
+            .ok()
+            .and_then(|json| json.parse::<Nip46Response>().ok())
+        else {
+            return;
+        };
+        let remote_pubkey = self.remote_pubkey.borrow().clone();
+        match remote_pubkey {
+            // A `nostrconnect://` signer answers with the secret, from its own pubkey
+            None => {
+                if response.result.is_some() && response.result == self.secret {
+                    *self.remote_pubkey.borrow_mut() = Some(note.pubkey.clone());
+                    if let Some(on_answer) = self.on_answer.borrow_mut().take() {
+                        let _ = on_answer.send(());
+                    }
+                }
+            }
+            Some(remote_pubkey) if remote_pubkey == note.pubkey => {
+                if let Some(url) = response.auth_url() {
+                    // The signer answers again once the user approves
+                    if let Some(window) = web_sys::window() {
+                        let _ = window.open_with_url_and_target(url, "_blank");
+                    }
+                    return;
+                }
+                if let Some(sender) = self.pending.borrow_mut().remove(&response.id) {
+                    let _ = sender.send(response);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    async fn request(&self, method: &str, params: Vec<String>) -> Result<String, SignerError> {
+        let remote_pubkey = self
+            .remote_pubkey
+            .borrow()
+            .clone()
+            .ok_or_else(|| SignerError::Rejected("signer not connected".to_string()))?;
+        let request = Nip46Request {
+            id: random_token(),
+            method: method.to_string(),
+            params,
+        };
+        let content = self
+            .client
+            .nip_44_encrypt(&request.to_string(), &remote_pubkey)
+            .map_err(rejected)?
+            .to_string();
+        let mut note = NostrNote {
+            pubkey: self.client_pubkey(),
+            created_at: chrono::Utc::now().timestamp(),
+            kind: NIP46_KIND,
+            content,
+            ..Default::default()
+        };
+        note.tags.add_pubkey_tag(&remote_pubkey);
+        self.client.sign_note(&mut note).map_err(rejected)?;
+        let (sender, receiver) = oneshot::channel();
+        self.pending.borrow_mut().insert(request.id.clone(), sender);
+        // Dropping the sender of an unanswered request ends the wait below
+        let pending = self.pending.clone();
+        yew::platform::spawn_local(async move {
+            gloo::timers::future::TimeoutFuture::new(REMOTE_REQUEST_TIMEOUT_MS).await;
+            pending.borrow_mut().remove(&request.id);
+        });
+        self.outbox.emit(note);
+        let response = receiver.await.map_err(|_| no_answer())?;
+        match (response.result, response.error) {
+            (_, Some(error)) if !error.is_empty() => Err(SignerError::Rejected(error)),
+            (Some(result), _) => Ok(result),
+            _ => Err(SignerError::Malformed),
+        }
+    }
+
+    /// Waits for the signer to accept the connection, returning the user's pubkey.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Rejected` if the signer refuses the connection or
+    /// doesn't answer within `REMOTE_REQUEST_TIMEOUT_MS`.
+    pub async fn connect(&self) -> Result<String, SignerError> {
+        let answered = self.answered.borrow_mut().take();
+        if let Some(answered) = answered {
+            // Same limit as a request, for a link that is never scanned
+            let on_answer = self.on_answer.clone();
+            yew::platform::spawn_local(async move {
+                gloo::timers::future::TimeoutFuture::new(REMOTE_REQUEST_TIMEOUT_MS).await;
+                on_answer.borrow_mut().take();
+            });
+            answered.await.map_err(|_| no_answer())?;
+        } else {
+            let remote_pubkey = self.remote_pubkey.borrow().clone().unwrap_or_default();
+            let params = std::iter::once(remote_pubkey)
+                .chain(self.secret.clone())
+                .collect();
+            self.request("connect", params).await?;
+        }
+        self.request("get_public_key", vec![]).await
+    }
+}
+
+/// What a NIP-46 login keeps to reconnect on the next visit.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RemoteLogin {
+    pub user_pubkey: String,
+    pub remote_pubkey: String,
+    pub relays: Vec<String>,
+    /// Key this app talks to the signer with, it can't sign as the user.
+    pub client_nsec: String,
+}
+
+/// Signs and encrypts as the logged in user, whoever holds their key.
+#[derive(Clone)]
+pub enum Signer {
+    /// Keys kept in this browser, signing through their `UserIdentity`.
+    Local {
+        identity: UserIdentity,
+        keys: NostrKeypair,
+        pubkey: String,
+    },
+    /// NIP-07 browser extension.
+    Extension { pubkey: String },
+    /// NIP-46 remote signer.
+    Remote {
+        session: RemoteSigner,
+        pubkey: String,
+    },
+}
+
+impl PartialEq for Signer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Local { pubkey: a, .. }, Self::Local { pubkey: b, .. })
+            | (Self::Extension { pubkey: a }, Self::Extension { pubkey: b }) => a == b,
+            (
+                Self::Remote {
+                    session: a,
+                    pubkey: pubkey_a,
+                },
+                Self::Remote {
+                    session: b,
+                    pubkey: pubkey_b,
+                },
+            ) => a == b && pubkey_a == pubkey_b,
+            _ => false,
+        }
+    }
+}
+
+impl Signer {
+    #[must_use]
+    pub fn pubkey(&self) -> &str {
+        match self {
+            Self::Local { pubkey, .. }
+            | Self::Extension { pubkey }
+            | Self::Remote { pubkey, .. } => pubkey,
+        }
+    }
+    /// Whether the key is stored in this browser, so it can be shown and backed up.
+    #[must_use]
+    pub const fn is_local(&self) -> bool {
+        matches!(self, Self::Local { .. })
+    }
+
+    /// Signs `note` as the user, filling in its pubkey, id and signature.
+    ///
+    /// # Errors
+    /// Returns `SignerError::Rejected` if the signer refuses, and
+    /// `SignerError::Malformed` if what it signed can't be read back.
+    pub async fn sign_note(&self, note: &mut NostrNote) -> Result<(), SignerError> {
+        note.pubkey = self.pubkey().to_string();
+        match self {
+            Self::Local { identity, .. } => identity.sign_nostr_note(note).await.map_err(rejected),
+            Self::Extension { .. } => {
+                let nostr = window_nostr().ok_or(SignerError::NoExtension)?;
+                let event =
+                    serde_wasm_bindgen::to_value(&*note).map_err(|_| SignerError::Malformed)?;
+                let signed = nostr.sign_event(event).await.map_err(js_rejected)?;
+                *note =
+                    serde_wasm_bindgen::from_value(signed).map_err(|_| SignerError::Malformed)?;
+                Ok(())
+            }
+            Self::Remote { session, .. } => {
+                let event = serde_json::to_string(&*note).map_err(|_| SignerError::Malformed)?;
+                let signed = session.request("sign_event", vec![event]).await?;
+                *note = serde_json::from_str(&signed).map_err(|_| SignerError::Malformed)?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn nip44_encrypt(&self, peer: &str, plaintext: &str) -> Result<String, SignerError> {
+        match self {
+            Self::Local { keys, .. } => keys
+                .nip_44_encrypt(plaintext, peer)
+                .map(|ciphertext| ciphertext.to_string())
+                .map_err(rejected),
+            Self::Extension { .. } => extension_nip44()?
+                .encrypt(peer, plaintext)
+                .await
+                .map_err(js_rejected)?
+                .as_string()
+                .ok_or(SignerError::Malformed),
+            Self::Remote { session, .. } => {
+                session
+                    .request(
+                        "nip44_encrypt",
+                        vec![peer.to_string(), plaintext.to_string()],
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn nip44_decrypt(&self, peer: &str, ciphertext: &str) -> Result<String, SignerError> {
+        match self {
+            Self::Local { keys, .. } => keys
+                .nip_44_decrypt(ciphertext, peer)
+                .map(|plaintext| plaintext.to_string())
+                .map_err(rejected),
+            Self::Extension { .. } => extension_nip44()?
+                .decrypt(peer, ciphertext)
+                .await
+                .map_err(js_rejected)?
+                .as_string()
+                .ok_or(SignerError::Malformed),
+            Self::Remote { session, .. } => {
+                session
+                    .request(
+                        "nip44_decrypt",
+                        vec![peer.to_string(), ciphertext.to_string()],
+                    )
+                    .await
+            }
+        }
+    }
+
+    /// NIP-17 direct message of `content` to `recipient`, gift wrapped and ready to send.
+    ///
+    /// # Errors
+    /// Returns an error if the signer refuses to seal the message.
+    pub async fn private_dm(
+        &self,
+        content: &str,
+        recipient: &str,
+    ) -> Result<NostrNote, SignerError> {
+        if let Self::Local { keys, .. } = self {
+            return keys.private_dm(content, recipient).map_err(rejected);
+        }
+        let tweaked = || {
+            chrono::Utc::now().timestamp()
+                - (web_sys::js_sys::Math::random() * TIMESTAMP_TWEAK_SECS) as i64
+        };
+        let mut rumor = NostrNote {
+            pubkey: self.pubkey().to_string(),
+            created_at: chrono::Utc::now().timestamp(),
+            kind: CHAT_MESSAGE_KIND,
+            content: content.to_string(),
+            ..Default::default()
+        };
+        rumor.tags.add_pubkey_tag(recipient);
+        rumor.serialize_id().map_err(rejected)?;
+        // The rumor is never signed, so it can't be proven to others
+        let rumor = serde_json::to_string(&rumor).map_err(|_| SignerError::Malformed)?;
+        let mut seal = NostrNote {
+            created_at: tweaked(),
+            kind: SEAL_KIND,
+            content: self.nip44_encrypt(recipient, &rumor).await?,
+            ..Default::default()
+        };
+        self.sign_note(&mut seal).await?;
+        let seal = serde_json::to_string(&seal).map_err(|_| SignerError::Malformed)?;
+        // Wrapped with a throwaway key, relays only see who it's for
+        let wrapper = NostrKeypair::generate(false);
+        let mut gift_wrap = NostrNote {
+            pubkey: wrapper.public_key(),
+            created_at: tweaked(),
+            kind: GIFT_WRAP_KIND,
+            content: wrapper
+                .nip_44_encrypt(&seal, recipient)
+                .map_err(rejected)?
+                .to_string(),
+            ..Default::default()
+        };
+        gift_wrap.tags.add_pubkey_tag(recipient);
+        wrapper.sign_note(&mut gift_wrap).map_err(rejected)?;
+        Ok(gift_wrap)
+    }
+
+    /// Rumor of a NIP-17 direct message sent to the user.
+    ///
+    /// # Errors
+    /// Returns an error if the gift wrap isn't for the user, or its seal
+    /// doesn't come from the rumor's author.
+    pub async fn open_gift_wrap(&self, gift_wrap: &NostrNote) -> Result<NostrNote, SignerError> {
+        if let Self::Local { keys, .. } = self {
+            return keys.extract_rumor(gift_wrap).map_err(rejected);
+        }
+        let seal = self
+            .nip44_decrypt(&gift_wrap.pubkey, &gift_wrap.content)
+            .await?;
+        let seal = serde_json::from_str::<NostrNote>(&seal).map_err(|_| SignerError::Malformed)?;
+        // Decrypting proves the seal comes from its pubkey, the rumor has to match it
+        let rumor = self.nip44_decrypt(&seal.pubkey, &seal.content).await?;
+        let rumor =
+            serde_json::from_str::<NostrNote>(&rumor).map_err(|_| SignerError::Malformed)?;
+        if seal.kind != SEAL_KIND || rumor.pubkey != seal.pubkey {
+            return Err(SignerError::Malformed);
+        }
+        Ok(rumor)
+    }
+}
+
+/// How the user logged in, when their key isn't kept by the key manager.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum LoginMethod {
+    Extension { pubkey: String },
+    Remote(RemoteLogin),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LoginMethodEntry {
+    id: String,
+    pub method: LoginMethod,
+}
+
+impl LoginMethodEntry {
+    pub const ID: &'static str = "login_method";
+
+    #[must_use]
+    pub fn new(method: LoginMethod) -> Self {
+        Self {
+            id: Self::ID.to_string(),
+            method,
+        }
+    }
+    /// Forgets the stored login, whatever its method.
+    pub async fn forget() {
+        use nostr_minions::browser_api::IdbStoreManager;
+        // Deleting only goes by `ID`, the method is never read
+        let entry = Self::new(LoginMethod::Extension {
+            pubkey: String::new(),
+        });
+        if let Err(e) = entry.delete_from_store().await {
+            web_sys::console::log_1(&format!("Failed to delete login method: {e:?}").into());
+        }
+    }
+}
+
+impl TryFrom<JsValue> for LoginMethodEntry {
+    type Error = JsValue;
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        Ok(serde_wasm_bindgen::from_value(value)?)
+    }
+}
+impl From<LoginMethodEntry> for JsValue {
+    fn from(value: LoginMethodEntry) -> Self {
+        serde_wasm_bindgen::to_value(&value).unwrap_or_default()
+    }
+}
+
+impl nostr_minions::browser_api::IdbStoreManager for LoginMethodEntry {
+    fn config() -> nostr_minions::browser_api::IdbStoreConfig {
+        nostr_minions::browser_api::IdbStoreConfig {
+            db_name: "annotator_login_db",
+            store_name: "annotator_login_store",
+            db_version: 1,
+            document_key: "id",
+        }
+    }
+    fn key(&self) -> JsValue {
+        JsValue::from_str(&self.id)
+    }
+}
@@ -0,0 +1,107 @@
+/// Why a game ended, written to the PGN `Termination` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminationReason {
+    Checkmate,
+    Stalemate,
+    Resignation,
+    TimeForfeit,
+    Agreement,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    InsufficientMaterial,
+    Abandonment,
+    ArbiterDecision,
+}
+
+impl TerminationReason {
+    pub const ALL: [Self; 10] = [
+        Self::Checkmate,
+        Self::Resignation,
+        Self::TimeForfeit,
+        Self::Agreement,
+        Self::ThreefoldRepetition,
+        Self::FiftyMoveRule,
+        Self::InsufficientMaterial,
+        Self::Stalemate,
+        Self::Abandonment,
+        Self::ArbiterDecision,
+    ];
+
+    /// Value of the PGN `Termination` header.
+    ///
+    /// The standard values are used where they exist (`time forfeit`,
+    /// `abandoned`, `adjudication`), the others name the reason since
+    /// `normal` alone would lose it.
+    #[must_use]
+    pub const fn pgn_value(self) -> &'static str {
+        match self {
+            Self::Checkmate => "checkmate",
+            Self::Stalemate => "stalemate",
+            Self::Resignation => "resignation",
+            Self::TimeForfeit => "time forfeit",
+            Self::Agreement => "agreement",
+            Self::ThreefoldRepetition => "threefold repetition",
+            Self::FiftyMoveRule => "fifty-move rule",
+            Self::InsufficientMaterial => "insufficient material",
+            Self::Abandonment => "abandoned",
+            Self::ArbiterDecision => "adjudication",
+        }
+    }
+    #[must_use]
+    pub fn from_pgn_value(value: &str) -> Option<Self> {
+        let value = value.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "time forfeit" => Some(Self::TimeForfeit),
+            "abandoned" => Some(Self::Abandonment),
+            "adjudication" | "rules infraction" => Some(Self::ArbiterDecision),
+            "50-move rule" => Some(Self::FiftyMoveRule),
+            "repetition" => Some(Self::ThreefoldRepetition),
+            _ => Self::ALL
+                .into_iter()
+                .find(|reason| reason.pgn_value() == value),
+        }
+    }
+    /// Result the reason implies on its own, the draws.
+    #[must_use]
+    pub const fn implied_outcome(self) -> Option<shakmaty::Outcome> {
+        match self {
+            Self::Stalemate
+            | Self::Agreement
+            | Self::ThreefoldRepetition
+            | Self::FiftyMoveRule
+            | Self::InsufficientMaterial => Some(shakmaty::Outcome::Draw),
+            _ => None,
+        }
+    }
+    /// Reason a position ends the game by the rules, if it does.
+    /// Variant wins like a king reaching the hill have no reason of their own.
+    #[must_use]
+    pub fn of_position(position: &shakmaty::variant::VariantPosition) -> Option<Self> {
+        use shakmaty::Position;
+        if position.is_variant_end() {
+            None
+        } else if position.is_checkmate() {
+            Some(Self::Checkmate)
+        } else if position.is_stalemate() {
+            Some(Self::Stalemate)
+        } else if position.is_insufficient_material() {
+            Some(Self::InsufficientMaterial)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.pgn_value())
+    }
+}
+
+impl std::str::FromStr for TerminationReason {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_pgn_value(s).ok_or(())
+    }
+}
@@ -0,0 +1,246 @@
+use shakmaty::Position;
+
+/// Rule set of a game.
+///
+/// Chess960 plays by the standard rules with shuffled back ranks, so unlike
+/// the other variants it is told apart by its castling mode.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GameVariant {
+    #[default]
+    Standard,
+    Chess960,
+    Crazyhouse,
+    KingOfTheHill,
+    ThreeCheck,
+    Atomic,
+    Antichess,
+    Horde,
+    RacingKings,
+}
+
+impl GameVariant {
+    pub const ALL: [Self; 9] = [
+        Self::Standard,
+        Self::Chess960,
+        Self::Crazyhouse,
+        Self::KingOfTheHill,
+        Self::ThreeCheck,
+        Self::Atomic,
+        Self::Antichess,
+        Self::Horde,
+        Self::RacingKings,
+    ];
+
+    #[must_use]
+    pub const fn variant(self) -> shakmaty::variant::Variant {
+        match self {
+            Self::Standard | Self::Chess960 => shakmaty::variant::Variant::Chess,
+            Self::Crazyhouse => shakmaty::variant::Variant::Crazyhouse,
+            Self::KingOfTheHill => shakmaty::variant::Variant::KingOfTheHill,
+            Self::ThreeCheck => shakmaty::variant::Variant::ThreeCheck,
+            Self::Atomic => shakmaty::variant::Variant::Atomic,
+            Self::Antichess => shakmaty::variant::Variant::Antichess,
+            Self::Horde => shakmaty::variant::Variant::Horde,
+            Self::RacingKings => shakmaty::variant::Variant::RacingKings,
+        }
+    }
+    #[must_use]
+    pub const fn castling_mode(self) -> shakmaty::CastlingMode {
+        match self {
+            Self::Chess960 => shakmaty::CastlingMode::Chess960,
+            _ => shakmaty::CastlingMode::Standard,
+        }
+    }
+    /// Value of the PGN `Variant` header.
+    #[must_use]
+    pub const fn pgn_name(self) -> &'static str {
+        match self {
+            Self::Standard => "Standard",
+            Self::Chess960 => "Chess960",
+            Self::Crazyhouse => "Crazyhouse",
+            Self::KingOfTheHill => "King of the Hill",
+            Self::ThreeCheck => "Three-check",
+            Self::Atomic => "Atomic",
+            Self::Antichess => "Antichess",
+            Self::Horde => "Horde",
+            Self::RacingKings => "Racing Kings",
+        }
+    }
+    /// Reads a PGN `Variant` header, accepting the usual alternative spellings.
+    #[must_use]
+    pub fn from_pgn_name(name: &str) -> Option<Self> {
+        let name = name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match name.as_str() {
+            "standard" | "chess" | "normal" => Some(Self::Standard),
+            "chess960" | "fischerandom" | "fischerrandom" | "960" => Some(Self::Chess960),
+            "crazyhouse" => Some(Self::Crazyhouse),
+            "kingofthehill" | "koth" => Some(Self::KingOfTheHill),
+            "threecheck" | "3check" => Some(Self::ThreeCheck),
+            "atomic" => Some(Self::Atomic),
+            "antichess" | "suicide" | "giveaway" => Some(Self::Antichess),
+            "horde" => Some(Self::Horde),
+            "racingkings" => Some(Self::RacingKings),
+            _ => None,
+        }
+    }
+    #[must_use]
+    pub fn of_position(position: &shakmaty::variant::VariantPosition) -> Self {
+        match position.variant() {
+            shakmaty::variant::Variant::Chess
+                if position.castles().mode() == shakmaty::CastlingMode::Chess960 =>
+            {
+                Self::Chess960
+            }
+            shakmaty::variant::Variant::Chess => Self::Standard,
+            shakmaty::variant::Variant::Crazyhouse => Self::Crazyhouse,
+            shakmaty::variant::Variant::KingOfTheHill => Self::KingOfTheHill,
+            shakmaty::variant::Variant::ThreeCheck => Self::ThreeCheck,
+            shakmaty::variant::Variant::Atomic => Self::Atomic,
+            shakmaty::variant::Variant::Antichess => Self::Antichess,
+            shakmaty::variant::Variant::Horde => Self::Horde,
+            shakmaty::variant::Variant::RacingKings => Self::RacingKings,
+        }
+    }
+    /// Usual starting position, the standard arrangement for Chess960.
+    #[must_use]
+    pub fn start_position(self) -> shakmaty::variant::VariantPosition {
+        match self {
+            Self::Chess960 => chess960_position(518),
+            _ => shakmaty::variant::VariantPosition::new(self.variant()),
+        }
+    }
+    /// Starting position for a new game, drawn at random for Chess960.
+    #[must_use]
+    pub fn new_game_position(self) -> shakmaty::variant::VariantPosition {
+        match self {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Self::Chess960 => {
+                chess960_position((web_sys::js_sys::Math::random() * 960.0) as u32 % 960)
+            }
+            _ => self.start_position(),
+        }
+    }
+    /// Whether pieces can be dropped from the pocket.
+    #[must_use]
+    pub const fn has_drops(self) -> bool {
+        matches!(self, Self::Crazyhouse)
+    }
+}
+
+impl std::fmt::Display for GameVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.pgn_name())
+    }
+}
+
+impl std::str::FromStr for GameVariant {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_pgn_name(s).ok_or(())
+    }
+}
+
+/// Chess960 starting position number `number`, following Scharnagl's numbering
+/// where 518 is the standard arrangement.
+fn chess960_position(number: u32) -> shakmaty::variant::VariantPosition {
+    let mut rank = [None::<char>; 8];
+    let mut n = number as usize % 960;
+    rank[(n % 4) * 2 + 1] = Some('B');
+    n /= 4;
+    rank[(n % 4) * 2] = Some('B');
+    n /= 4;
+    let mut place_on_empty = |index: usize, piece: char| {
+        let empty = (0..8).filter(|file| rank[*file].is_none()).nth(index);
+        if let Some(file) = empty {
+            rank[file] = Some(piece);
+        }
+    };
+    place_on_empty(n % 6, 'Q');
+    n /= 6;
+    let (first_knight, second_knight) = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (0, 4),
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (2, 3),
+        (2, 4),
+        (3, 4),
+    ][n];
+    // The second knight is placed first so the first index doesn't shift
+    place_on_empty(second_knight, 'N');
+    place_on_empty(first_knight, 'N');
+    for piece in ['R', 'K', 'R'] {
+        place_on_empty(0, piece);
+    }
+    let white = rank
+        .iter()
+        .map(|piece| piece.unwrap_or('R'))
+        .collect::<String>();
+    // Shredder-FEN castling rights name the files of the rooks
+    let castling = white
+        .char_indices()
+        .filter(|(_, piece)| *piece == 'R')
+        .filter_map(|(file, _)| shakmaty::File::ALL.get(file))
+        .map(|file| file.upper_char())
+        .collect::<String>();
+    let fen = format!(
+        "{}/pppppppp/8/8/8/8/PPPPPPPP/{white} w {castling}{} - 0 1",
+        white.to_ascii_lowercase(),
+        castling.to_ascii_lowercase()
+    );
+    position_from_fen(&fen, GameVariant::Chess960)
+        .unwrap_or_else(|_| GameVariant::Standard.start_position())
+}
+
+/// Why a FEN can't start a game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FenError {
+    /// Not a FEN at all.
+    Invalid,
+    /// A FEN of a position that can't come up in the variant.
+    IllegalPosition,
+}
+
+impl std::fmt::Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid => write!(f, "Invalid FEN"),
+            Self::IllegalPosition => write!(f, "Illegal position"),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+/// Parses and validates a starting position of `variant`.
+///
+/// # Errors
+///
+/// Returns `FenError::Invalid` if the FEN can't be parsed, and
+/// `FenError::IllegalPosition` if it describes an impossible position.
+pub fn position_from_fen(
+    fen: &str,
+    variant: GameVariant,
+) -> Result<shakmaty::variant::VariantPosition, FenError> {
+    let setup = fen
+        .trim()
+        .parse::<shakmaty::fen::Fen>()
+        .map_err(|_| FenError::Invalid)?
+        .into_setup();
+    shakmaty::variant::VariantPosition::from_setup(
+        variant.variant(),
+        setup,
+        variant.castling_mode(),
+    )
+    .map_err(|_| FenError::IllegalPosition)
+}
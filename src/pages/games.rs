@@ -0,0 +1,380 @@
+use std::rc::Rc;
+
+use crate::router::AnnotatorRoute;
+use nostr_minions::browser_api::IdbStoreManager;
+use shady_minions::ui::{Button, Card, CardContent, CardHeader, CardTitle};
+use web_sys::MouseEvent;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// A stored game with its PGN already read, `None` if the note didn't hold a valid game.
+#[derive(Clone)]
+struct GameRow {
+    entry: rooky_core::idb::RookyGameEntry,
+    game: Option<crate::models::PgnGame>,
+    /// Whether the note was found on the relays, so it survives a change of device.
+    synced: bool,
+}
+
+impl GameRow {
+    fn new(entry: rooky_core::idb::RookyGameEntry, synced: bool) -> Self {
+        Self {
+            game: crate::models::parse_pgn(&entry.note.content),
+            entry,
+            synced,
+        }
+    }
+}
+
+/// Games saved on this device merged with the ones published from any device.
+#[derive(Clone, Default)]
+struct GameHistory {
+    rows: Vec<GameRow>,
+    /// Whether the local games were read, relay notes wait for them to be merged.
+    loaded: bool,
+}
+
+enum GameHistoryAction {
+    Loaded(Vec<rooky_core::idb::RookyGameEntry>),
+    /// Game notes received from the relays, de-duplicated by note id.
+    Synced(Vec<rooky_core::idb::RookyGameEntry>),
+    Deleted(String),
+}
+
+impl Reducible for GameHistory {
+    type Action = GameHistoryAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut rows = self.rows.clone();
+        let loaded = self.loaded || matches!(action, GameHistoryAction::Loaded(_));
+        match action {
+            GameHistoryAction::Loaded(entries) => {
+                rows.extend(
+                    entries
+                        .into_iter()
+                        .filter(|entry| !self.rows.iter().any(|row| row.entry.id == entry.id))
+                        .map(|entry| GameRow::new(entry, false)),
+                );
+            }
+            GameHistoryAction::Synced(entries) => {
+                for entry in entries {
+                    match rows.iter_mut().find(|row| row.entry.id == entry.id) {
+                        Some(row) => row.synced = true,
+                        None => rows.push(GameRow::new(entry, true)),
+                    }
+                }
+            }
+            GameHistoryAction::Deleted(id) => rows.retain(|row| row.entry.id != id),
+        }
+        rows.sort_by(|a, b| b.entry.note.created_at.cmp(&a.entry.note.created_at));
+        Rc::new(Self { rows, loaded })
+    }
+}
+
+#[function_component(GamesPage)]
+pub fn games_page() -> Html {
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
+        .expect("Relay context not found");
+    let signer_ctx = crate::signer::use_signer();
+    let games = use_reducer(GameHistory::default);
+    let pubkey = use_state(|| None::<String>);
+    let ondelete = {
+        let games = games.clone();
+        Callback::from(move |id: String| {
+            let Some(entry) = games
+                .rows
+                .iter()
+                .find(|row| row.entry.id == id)
+                .map(|row| row.entry.clone())
+            else {
+                return;
+            };
+            let games = games.clone();
+            yew::platform::spawn_local(async move {
+                if entry.delete_from_store().await.is_err() {
+                    web_sys::console::log_1(&format!("Failed to delete game: {}", id).into());
+                    return;
+                }
+                games.dispatch(GameHistoryAction::Deleted(id));
+            });
+        })
+    };
+    {
+        let games = games.clone();
+        use_effect_with((), move |()| {
+            yew::platform::spawn_local(async move {
+                let Ok(entries) = rooky_core::idb::RookyGameEntry::retrieve_all_from_store().await
+                else {
+                    web_sys::console::log_1(&"Failed to retrieve games".into());
+                    return;
+                };
+                games.dispatch(GameHistoryAction::Loaded(entries));
+            });
+            || {}
+        });
+    }
+    // Asks the relays for every game we published, from this device or another one
+    {
+        let relay_ctx = relay_ctx.clone();
+        let pubkey = pubkey.clone();
+        use_effect_with(signer_ctx.pubkey(), move |author| {
+            if let Some(author) = author {
+                relay_ctx.send(nostr_minions::nostro2::NostrSubscription {
+                    kinds: Some(vec![crate::components::game_note_kind()]),
+                    authors: Some(vec![author.clone()]),
+                    ..Default::default()
+                });
+                pubkey.set(Some(author.clone()));
+            }
+            || {}
+        });
+    }
+    {
+        let games = games.clone();
+        use_effect_with(
+            (
+                relay_ctx.unique_notes.clone(),
+                (*pubkey).clone(),
+                games.loaded,
+            ),
+            move |(notes, pubkey, loaded)| {
+                if let (Some(pubkey), true) = (pubkey, *loaded) {
+                    let kind = crate::components::game_note_kind();
+                    let entries = notes
+                        .iter()
+                        .filter(|note| note.kind == kind && &note.pubkey == pubkey)
+                        .filter_map(|note| {
+                            Some(rooky_core::idb::RookyGameEntry {
+                                id: note.id.clone()?,
+                                note: note.clone(),
+                                origin: rooky_core::idb::GameOrigin::Annotated,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    // Games from other devices are kept here too, to review them offline later
+                    for entry in &entries {
+                        if !games.rows.iter().any(|row| row.entry.id == entry.id) {
+                            let entry = entry.clone();
+                            yew::platform::spawn_local(async move {
+                                if let Err(e) = entry.save_to_store().await {
+                                    web_sys::console::error_1(&e);
+                                }
+                            });
+                        }
+                    }
+                    if !entries.is_empty() {
+                        games.dispatch(GameHistoryAction::Synced(entries));
+                    }
+                }
+                || {}
+            },
+        );
+    }
+
+    html! {
+        <>
+            <yew_router::components::Link<AnnotatorRoute> to={AnnotatorRoute::Home}>
+                <Button
+                    class="fixed top-4 left-4 z-50"
+                    variant={shady_minions::ui::ButtonVariant::Outline}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    >
+                    <lucide_yew::ArrowLeft class="size-4" />
+                </Button>
+            </yew_router::components::Link<AnnotatorRoute>>
+            <Card class="max-w-sm h-fit mx-auto mt-16">
+                <CardHeader>
+                    <CardTitle>{ language_ctx.t("games_title") }</CardTitle>
+                </CardHeader>
+                <CardContent>
+                    {if games.rows.is_empty() {
+                        html! {
+                            <div class="text-center py-8 text-muted-foreground">
+                                <lucide_yew::Library class="w-12 h-12 mx-auto mb-2 opacity-50" />
+                                <p>{ language_ctx.t("games_empty") }</p>
+                            </div>
+                        }
+                    } else {
+                        html! {
+                            <div class="space-y-3">
+                                { for games.rows.iter().map(|row| html! {
+                                    <GameRowCard
+                                        key={row.entry.id.clone()}
+                                        id={row.entry.id.clone()}
+                                        game={row.game.clone()}
+                                        synced={row.synced}
+                                        ondelete={ondelete.clone()} />
+                                }) }
+                            </div>
+                        }
+                    }}
+                </CardContent>
+            </Card>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+struct GameRowCardProps {
+    id: String,
+    game: Option<crate::models::PgnGame>,
+    synced: bool,
+    ondelete: Callback<String>,
+}
+
+#[function_component(GameRowCard)]
+fn game_row_card(props: &GameRowCardProps) -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let navigator = use_navigator().expect("Navigator not found");
+    let delete = {
+        let ondelete = props.ondelete.clone();
+        let id = props.id.clone();
+        Callback::from(move |_: MouseEvent| ondelete.emit(id.clone()))
+    };
+    let Some(game) = props.game.clone() else {
+        return html! {
+            <div class="flex items-center justify-between gap-2 p-3 border border-muted rounded-md">
+                <span class="text-sm text-muted-foreground">{ language_ctx.t("games_unreadable") }</span>
+                <Button
+                    onclick={delete}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Destructive}>
+                    <lucide_yew::Trash2 class="size-4" />
+                </Button>
+            </div>
+        };
+    };
+    let header = |name: &str| {
+        game.header(name)
+            .filter(|value| *value != "?")
+            .unwrap_or_default()
+            .to_string()
+    };
+    let date = game
+        .header("Date")
+        .and_then(|date| {
+            crate::contexts::formatting::format_pgn_date(language_ctx.current_locale(), date)
+        })
+        .unwrap_or_default();
+
+    let open_review = {
+        let navigator = navigator.clone();
+        let id = props.id.clone();
+        Callback::from(move |_: MouseEvent| {
+            navigator.push(&AnnotatorRoute::StoredGame { id: id.clone() });
+        })
+    };
+    let load_for_editing = {
+        let entry = crate::live_game::LiveGameEntry::from_pgn_game(&game);
+        let message = language_ctx.t("games_replace_current");
+        Callback::from(move |_: MouseEvent| {
+            if crate::components::replace_live_game(&game_ctx, entry.clone(), &message) {
+                navigator.push(&AnnotatorRoute::Home);
+            }
+        })
+    };
+
+    html! {
+        <div class="flex items-center justify-between gap-2 p-3 border border-muted rounded-md">
+            <div class="flex flex-col overflow-hidden">
+                <span class="font-medium truncate flex items-center gap-1">
+                    if props.synced {
+                        <lucide_yew::Cloud class="size-4 shrink-0 text-muted-foreground" />
+                    }
+                    { format!("{} - {}", header("White"), header("Black")) }
+                </span>
+                <span class="text-xs text-muted-foreground truncate">
+                    { [date, header("Event"), header("Result")]
+                        .into_iter()
+                        .filter(|value| !value.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" · ") }
+                </span>
+            </div>
+            <div class="flex gap-1">
+                <Button
+                    onclick={open_review}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::Eye class="size-4" />
+                </Button>
+                <Button
+                    onclick={load_for_editing}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::Pencil class="size-4" />
+                </Button>
+                <Button
+                    onclick={delete}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Destructive}>
+                    <lucide_yew::Trash2 class="size-4" />
+                </Button>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct StoredGameReviewProps {
+    pub id: String,
+}
+
+/// Review page for a game from the library, shown without replacing the live game.
+#[function_component(StoredGameReviewPage)]
+pub fn stored_game_review_page(props: &StoredGameReviewProps) -> Html {
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    // `None` while loading, `Some(None)` if the game is missing or can't be read
+    let entry = use_state(|| None::<Option<crate::live_game::LiveGameEntry>>);
+    {
+        let entry = entry.clone();
+        use_effect_with(props.id.clone(), move |id| {
+            // The game of the previous id isn't shown while the next one loads
+            entry.set(None);
+            let id = id.clone();
+            yew::platform::spawn_local(async move {
+                let stored = rooky_core::idb::RookyGameEntry::retrieve_from_store::<
+                    rooky_core::idb::RookyGameEntry,
+                >(&id.as_str().into())
+                .await;
+                match stored {
+                    Ok(stored) => {
+                        let game = crate::models::parse_pgn(&stored.note.content)
+                            .map(|game| crate::live_game::LiveGameEntry::from_pgn_game(&game));
+                        entry.set(Some(game));
+                    }
+                    Err(e) => {
+                        web_sys::console::error_1(&format!("Error loading game: {:?}", e).into());
+                        entry.set(Some(None));
+                    }
+                }
+            });
+            || {}
+        });
+    }
+
+    match (*entry).clone() {
+        Some(Some(entry)) => html! {
+            <crate::live_game::StoredGameProvider key={props.id.clone()} {entry}>
+                <crate::ReviewPage />
+            </crate::live_game::StoredGameProvider>
+        },
+        Some(None) => html! {
+            <Card class="max-w-sm h-fit mx-auto mt-16">
+                <CardContent class="text-center py-8 space-y-4 text-muted-foreground">
+                    <lucide_yew::FileX class="w-12 h-12 mx-auto opacity-50" />
+                    <p>{ language_ctx.t("games_not_found") }</p>
+                    <yew_router::components::Link<AnnotatorRoute> to={AnnotatorRoute::Games}>
+                        <Button variant={shady_minions::ui::ButtonVariant::Outline}>
+                            <lucide_yew::ArrowLeft class="size-4 mr-2" />
+                            { language_ctx.t("games_back_to_library") }
+                        </Button>
+                    </yew_router::components::Link<AnnotatorRoute>>
+                </CardContent>
+            </Card>
+        },
+        None => html! {},
+    }
+}
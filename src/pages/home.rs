@@ -1,5 +1,7 @@
 use crate::components::UserProfileCard;
+use crate::models::TerminationReason;
 use crate::router::AnnotatorRoute;
+use nostr_minions::browser_api::IdbStoreManager;
 use shady_minions::ui::{
     Button, Card, CardContent, CardHeader, CardTitle, Input, LeftDrawer, Modal, Select,
     SelectContent, SelectItem, SelectTrigger, Switch, Tabs, TabsContent, TabsList, TabsTrigger,
@@ -23,6 +25,7 @@ pub fn home_page() -> Html {
                     <div class="flex gap-1 h-fit w-full items-center">
                         <TakeBackButton />
                         <MoveList />
+                        <HistoryButtons />
                         <Button
                             onclick={
                                 let outcome_open = outcome_open.clone();
@@ -35,6 +38,10 @@ pub fn home_page() -> Html {
                             <lucide_yew::Handshake class="size-7" />
                         </Button>
                     </div>
+                    <MoveNavigation />
+                    <crate::components::ChessClockPanel />
+                    <DrawClaimOffer />
+                    <BroadcastToggle />
                     <div class="h-[0.5px] bg-muted my-3 w-full px-3 sm:px-6 rounded-lg" />
                     <TabsContent
                         class="flex flex-col justify-between"
@@ -51,6 +58,7 @@ pub fn home_page() -> Html {
             <Modal is_open={outcome_open} >
                 <OutcomeForm />
             </Modal>
+            <crate::components::ResumeGamePrompt />
         </>
     }
 }
@@ -64,6 +72,18 @@ pub fn take_back_button() -> Html {
             game_ctx.dispatch(crate::live_game::AnnotatedGameAction::TakeBack);
         })
     };
+    crate::components::use_keydown(
+        (),
+        Callback::from(move |event: web_sys::KeyboardEvent| {
+            if (event.ctrl_key() || event.meta_key())
+                && event.key() == crate::components::TAKE_BACK_KEY
+                && !crate::components::is_typing_in_field(&event)
+            {
+                event.prevent_default();
+                game_ctx.dispatch(crate::live_game::AnnotatedGameAction::TakeBack);
+            }
+        }),
+    );
     html! {
         <shady_minions::ui::Button
             size={shady_minions::ui::ButtonSize::Icon}
@@ -79,6 +99,69 @@ pub fn take_back_button() -> Html {
     }
 }
 
+/// Undo and redo of every edit to the game, also on Ctrl+Z and Ctrl+Y (or Ctrl+Shift+Z).
+#[function_component(HistoryButtons)]
+pub fn history_buttons() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let dispatch = |action: fn() -> crate::live_game::AnnotatedGameAction| {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| game_ctx.dispatch(action()))
+    };
+    let undo = dispatch(|| crate::live_game::AnnotatedGameAction::Undo);
+    let redo = dispatch(|| crate::live_game::AnnotatedGameAction::Redo);
+    {
+        let game_ctx = game_ctx.clone();
+        crate::components::use_keydown(
+            (),
+            Callback::from(move |event: web_sys::KeyboardEvent| {
+                if !(event.ctrl_key() || event.meta_key())
+                    || crate::components::is_typing_in_field(&event)
+                {
+                    return;
+                }
+                let key = event.key().to_lowercase();
+                let action = if key == crate::components::UNDO_KEY && !event.shift_key() {
+                    crate::live_game::AnnotatedGameAction::Undo
+                } else if key == crate::components::REDO_KEY
+                    || (key == crate::components::UNDO_KEY && event.shift_key())
+                {
+                    crate::live_game::AnnotatedGameAction::Redo
+                } else {
+                    return;
+                };
+                event.prevent_default();
+                game_ctx.dispatch(action);
+            }),
+        );
+    }
+    let variant = |enabled: bool| {
+        if enabled {
+            shady_minions::ui::ButtonVariant::Normal
+        } else {
+            shady_minions::ui::ButtonVariant::Disabled
+        }
+    };
+
+    html! {
+        <>
+            <Button
+                onclick={undo}
+                size={shady_minions::ui::ButtonSize::Icon}
+                variant={variant(game_ctx.can_undo())}
+                class="bg-transparent">
+                <lucide_yew::RotateCcw class="size-6" />
+            </Button>
+            <Button
+                onclick={redo}
+                size={shady_minions::ui::ButtonSize::Icon}
+                variant={variant(game_ctx.can_redo())}
+                class="bg-transparent">
+                <lucide_yew::RotateCw class="size-6" />
+            </Button>
+        </>
+    }
+}
+
 const OUTCOMES: [shakmaty::Outcome; 3] = [
     shakmaty::Outcome::Draw,
     shakmaty::Outcome::Decisive {
@@ -93,6 +176,7 @@ pub fn outcome_form() -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
     let language_ctx = crate::contexts::language::use_language_ctx();
     let outcome_state = use_state(|| shakmaty::Outcome::Draw);
+    let termination_state = use_state(|| None::<TerminationReason>);
     let navigator = use_navigator().unwrap();
 
     html! {
@@ -108,13 +192,19 @@ pub fn outcome_form() -> Html {
                     onsubmit={{
                         let game_ctx = game_ctx.clone();
                         let outcome_state = outcome_state.clone();
+                        let termination_state = termination_state.clone();
                         let navigator = navigator.clone();
                         Callback::from(move |_: web_sys::HtmlFormElement| {
-                            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::AddOutcome(
-                                *outcome_state,
-                            ));
+                            let termination = *termination_state;
+                            // Draw reasons decide the result on their own
+                            let outcome = termination
+                                .and_then(TerminationReason::implied_outcome)
+                                .unwrap_or(*outcome_state);
+                            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::AddOutcome {
+                                outcome,
+                                termination,
+                            });
                             navigator.push(&AnnotatorRoute::Review);
-
                         })
                     }}
                 >
@@ -134,22 +224,39 @@ pub fn outcome_form() -> Html {
                             { for OUTCOMES.iter().map(|outcome| {
                                 html! {
                                     <SelectItem::<shakmaty::Outcome> value={*outcome}
-                                        label={match *outcome {
-                                            shakmaty::Outcome::Draw => language_ctx.t("common_draw"),
-                                            shakmaty::Outcome::Decisive { winner } => {
-                                                if winner == shakmaty::Color::White {
-                                                    language_ctx.t("common_white_wins")
-                                                } else {
-                                                    language_ctx.t("common_black_wins")
-                                                }
-                                            }
-                                        }}
+                                        label={crate::contexts::formatting::outcome_text(&language_ctx, *outcome)}
                                     />
                                 }
                             }) }
                         </SelectContent::<shakmaty::Outcome>>
                     </Select::<shakmaty::Outcome>>
 
+                    <div class="space-y-2">
+                        <label class="text-sm font-medium text-foreground">
+                            { language_ctx.t("termination_title") }
+                        </label>
+                        <Select::<TerminationReason>
+                            name="termination"
+                            onchange={{
+                                let termination_state = termination_state.setter();
+                                Callback::from(move |value: Option<TerminationReason>| {
+                                    termination_state.set(value);
+                                })
+                            }}
+                            >
+                            <SelectTrigger::<TerminationReason> class="w-full" />
+                            <SelectContent::<TerminationReason>>
+                                { for TerminationReason::ALL.iter().map(|reason| {
+                                    html! {
+                                        <SelectItem::<TerminationReason> value={*reason}
+                                            label={crate::contexts::formatting::termination_text(&language_ctx, *reason)}
+                                        />
+                                    }
+                                }) }
+                            </SelectContent::<TerminationReason>>
+                        </Select::<TerminationReason>>
+                    </div>
+
                     <shady_minions::ui::Button
                         r#type={shady_minions::ui::ButtonType::Submit}
                         class="w-full mt-4"
@@ -157,18 +264,170 @@ pub fn outcome_form() -> Html {
                         { language_ctx.t("common_save") }
                     </shady_minions::ui::Button>
                 </shady_minions::ui::Form>
+                if game_ctx.has_outcome() {
+                    <Button
+                        onclick={{
+                            let game_ctx = game_ctx.clone();
+                            Callback::from(move |_: MouseEvent| {
+                                game_ctx.dispatch(crate::live_game::AnnotatedGameAction::ClearOutcome);
+                            })
+                        }}
+                        class="w-full mt-2"
+                        variant={shady_minions::ui::ButtonVariant::Destructive}>
+                        { language_ctx.t("outcome_clear") }
+                    </Button>
+                }
             </CardContent>
         </Card>
     }
 }
 
+/// Starts or stops broadcasting the game to spectators, with their link to copy.
+#[function_component(BroadcastToggle)]
+pub fn broadcast_toggle() -> Html {
+    let broadcast_ctx = crate::broadcast::use_broadcast();
+    let signer_ctx = crate::signer::use_signer();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let relay_pool = nostr_minions::relay_pool::use_nostr_relay_pool();
+    let copied = use_state(|| false);
+    let Some(pubkey) = signer_ctx.pubkey() else {
+        return html! {};
+    };
+    let toggle = {
+        let broadcast_ctx = broadcast_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            if broadcast_ctx.is_live() {
+                broadcast_ctx.dispatch(crate::broadcast::BroadcastAction::Stop);
+                return;
+            }
+            let broadcast_ctx = broadcast_ctx.clone();
+            let pubkey = pubkey.clone();
+            let pool = relay_pool.relay_health();
+            // The link tells spectators which relays the broadcast is written to
+            yew::platform::spawn_local(async move {
+                let relays = nostr_minions::relay_pool::UserRelay::retrieve_all_from_store()
+                    .await
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|relay| relay.write && pool.contains_key(relay.url.as_str()))
+                    .map(|relay| relay.url)
+                    .collect();
+                broadcast_ctx.dispatch(crate::broadcast::BroadcastAction::Start { pubkey, relays });
+            });
+        })
+    };
+    let copy_link = broadcast_ctx.naddr().map(|naddr| {
+        let copied = copied.clone();
+        Callback::from(move |_: MouseEvent| {
+            let origin = web_sys::window()
+                .and_then(|window| window.location().origin().ok())
+                .unwrap_or_default();
+            let route = AnnotatorRoute::Spectate {
+                naddr: naddr.to_string(),
+            };
+            nostr_minions::browser_api::clipboard_copy(&format!("{origin}{}", route.to_path()));
+            copied.set(true);
+            let copied = copied.clone();
+            gloo::timers::callback::Timeout::new(2000, move || {
+                copied.set(false);
+            })
+            .forget();
+        })
+    });
+
+    html! {
+        <div class="flex gap-2 items-center w-full mt-2">
+            <Button
+                onclick={toggle}
+                class="flex-1"
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={if broadcast_ctx.is_live() {
+                    shady_minions::ui::ButtonVariant::Destructive
+                } else {
+                    shady_minions::ui::ButtonVariant::Outline
+                }}>
+                <lucide_yew::Radio class="size-4" />
+                <span class="ml-2">
+                    { if broadcast_ctx.is_live() {
+                        language_ctx.t("broadcast_stop")
+                    } else {
+                        language_ctx.t("broadcast_start")
+                    } }
+                </span>
+            </Button>
+            if let Some(onclick) = copy_link {
+                <Button
+                    {onclick}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    if *copied {
+                        <lucide_yew::Check class="size-4" />
+                    } else {
+                        <lucide_yew::Link class="size-4" />
+                    }
+                    <span class="ml-2">{ language_ctx.t("broadcast_copy_link") }</span>
+                </Button>
+            }
+        </div>
+    }
+}
+
+/// Offers to end the game when the last move made a draw claimable.
+#[function_component(DrawClaimOffer)]
+pub fn draw_claim_offer() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let navigator = use_navigator().unwrap();
+    let Some(reason) = game_ctx.draw_claim() else {
+        return html! {};
+    };
+    let claim = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::AddOutcome {
+                outcome: shakmaty::Outcome::Draw,
+                termination: Some(reason),
+            });
+            navigator.push(&AnnotatorRoute::Review);
+        })
+    };
+    let dismiss = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::DismissDrawClaim);
+        })
+    };
+
+    html! {
+        <div class="flex gap-2 items-center w-full mt-2 rounded-md bg-background p-2">
+            <span class="flex-1 text-sm text-foreground">
+                { crate::contexts::formatting::termination_text(&language_ctx, reason) }
+            </span>
+            <Button onclick={claim} size={shady_minions::ui::ButtonSize::Small}>
+                { language_ctx.t("draw_claim_accept") }
+            </Button>
+            <Button
+                onclick={dismiss}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={shady_minions::ui::ButtonVariant::Outline}>
+                <lucide_yew::X class="size-4" />
+            </Button>
+        </div>
+    }
+}
+
 #[function_component(HomeHeader)]
 pub fn home_header() -> Html {
     html! {
         <header class="flex justify-between items-center px-6 gap-2 w-full mb-6">
             <SettingsDrawer />
             <ExperienceSelector />
-            <GameDetailsModal />
+            <div class="flex gap-2">
+                <crate::components::BoardSetup />
+                <crate::components::TimeControlSetup />
+                <crate::components::ImportPgn />
+                <GameDetailsModal />
+            </div>
         </header>
     }
 }
@@ -176,7 +435,7 @@ pub fn home_header() -> Html {
 #[function_component(SettingsDrawer)]
 pub fn settings_drawer() -> Html {
     let is_open = use_state(|| false);
-    let pubkey = nostr_minions::key_manager::use_nostr_pubkey();
+    let pubkey = crate::signer::use_signer().pubkey();
     let onclick = {
         let is_open = is_open.clone();
         Callback::from(move |_: MouseEvent| {
@@ -196,6 +455,15 @@ pub fn settings_drawer() -> Html {
         Callback::from(move |_: MouseEvent| navigator.push(&AnnotatorRoute::RelaySettings))
     };
 
+    let go_to_games = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| navigator.push(&AnnotatorRoute::Games))
+    };
+    let go_to_inbox = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| navigator.push(&AnnotatorRoute::Inbox))
+    };
+
     let set_experience_level = {
         let config_ctx = config_ctx.clone();
         move |level: crate::contexts::configs::ExperienceLevel| {
@@ -246,7 +514,25 @@ pub fn settings_drawer() -> Html {
                             variant={shady_minions::ui::ButtonVariant::Outline}
                         >
                             <lucide_yew::Wifi class="w-4 h-4 sm:w-5 sm:h-5 mr-1.5 sm:mr-2 flex-shrink-0 text-secondary" />
-                            <span class="font-medium truncate text-sm">{"Relay Management"}</span>
+                            <span class="font-medium truncate text-sm">{ language_ctx.t("settings_relay_management") }</span>
+                        </Button>
+
+                        <Button
+                            onclick={go_to_games}
+                            size={shady_minions::ui::ButtonSize::Small}
+                            variant={shady_minions::ui::ButtonVariant::Outline}
+                        >
+                            <lucide_yew::Library class="w-4 h-4 sm:w-5 sm:h-5 mr-1.5 sm:mr-2 flex-shrink-0 text-secondary" />
+                            <span class="font-medium truncate text-sm">{ language_ctx.t("games_title") }</span>
+                        </Button>
+
+                        <Button
+                            onclick={go_to_inbox}
+                            size={shady_minions::ui::ButtonSize::Small}
+                            variant={shady_minions::ui::ButtonVariant::Outline}
+                        >
+                            <lucide_yew::Inbox class="w-4 h-4 sm:w-5 sm:h-5 mr-1.5 sm:mr-2 flex-shrink-0 text-secondary" />
+                            <span class="font-medium truncate text-sm">{ language_ctx.t("inbox_title") }</span>
                         </Button>
                     </div>
                     <div class="border border-secondary w-full max-w-sm mx-auto my-6" />
@@ -265,7 +551,7 @@ pub fn settings_drawer() -> Html {
                         <div class="flex items-center justify-between">
                             <div class="flex items-center">
                                 <lucide_yew::Wifi class="w-4 h-4 sm:w-5 sm:h-5 mr-2 text-secondary" />
-                                <span class="text-sm sm:text-base font-medium text-muted ">{"Relay Status"}</span>
+                                <span class="text-sm sm:text-base font-medium text-muted ">{ language_ctx.t("settings_relay_status") }</span>
                             </div>
                             <RelayStatusIcon />
                         </div>
@@ -335,6 +621,73 @@ pub fn settings_drawer() -> Html {
                             </div>
                         </div>
                     </div>
+
+                    <div class="rounded-lg shadow-sm">
+                        <div class="mb-1.5 sm:mb-2">
+                            <label class="text-sm sm:text-base font-medium block mb-0.5 sm:mb-1 text-muted">
+                                { language_ctx.t("settings_language") }
+                            </label>
+                            <p class="text-xs sm:text-sm text-muted-foreground">
+                                { language_ctx.t("settings_language_description") }
+                            </p>
+                        </div>
+                        <div class="mt-2 flex flex-wrap gap-2 px-1">
+                            { for crate::contexts::configs::Language::ALL.iter().map(|language| {
+                                let onclick = {
+                                    let config_ctx = config_ctx.clone();
+                                    let language = *language;
+                                    Callback::from(move |_: MouseEvent| {
+                                        config_ctx.dispatch(
+                                            crate::contexts::configs::AnnotatorConfigAction::SetLanguage(language),
+                                        );
+                                    })
+                                };
+                                html! {
+                                    <Button
+                                        {onclick}
+                                        size={shady_minions::ui::ButtonSize::Small}
+                                        variant={if config_ctx.language == *language {
+                                            shady_minions::ui::ButtonVariant::Normal
+                                        } else {
+                                            shady_minions::ui::ButtonVariant::Outline
+                                        }}
+                                    >
+                                        { language.native_name() }
+                                    </Button>
+                                }
+                            }) }
+                        </div>
+                    </div>
+
+                    <div class="rounded-lg shadow-sm">
+                        <div class="mb-1.5 sm:mb-2">
+                            <label class="text-sm sm:text-base font-medium block mb-0.5 sm:mb-1 text-muted">
+                                { language_ctx.t("settings_figurine_notation") }
+                            </label>
+                            <p class="text-xs sm:text-sm text-muted-foreground">
+                                { language_ctx.t("settings_figurine_notation_description") }
+                            </p>
+                        </div>
+                        <div class="mt-2">
+                            <div class="flex items-center justify-between px-1">
+                                <div class="flex items-center">
+                                    <span class="text-sm font-medium mr-2 sm:mr-3 text-muted">{ config_ctx.san_notation().letter(shakmaty::Role::Knight).to_string() }</span>
+                                    <Switch
+                                        checked={config_ctx.figurine_notation}
+                                        onchange={
+                                            let config_ctx = config_ctx.clone();
+                                            Callback::from(move |checked: bool| {
+                                                config_ctx.dispatch(
+                                                    crate::contexts::configs::AnnotatorConfigAction::SetFigurineNotation(checked),
+                                                );
+                                            })
+                                        }
+                                    />
+                                </div>
+                                <span class="text-sm font-medium ml-2 sm:ml-3 text-muted">{ "♘" }</span>
+                            </div>
+                        </div>
+                    </div>
                 </div>
             </LeftDrawer>
         </>
@@ -345,15 +698,56 @@ pub fn settings_drawer() -> Html {
 pub fn experience_selector() -> Html {
     // Get language context
     let language_ctx = crate::contexts::language::use_language_ctx();
+    let config_ctx = crate::configs::use_annotator_config();
+    // The tabs keep their own selection, this follows it for the keyboard shortcut
+    let active_level = use_state(|| config_ctx.experience_level);
+    let rookie_ref = use_node_ref();
+    let expert_ref = use_node_ref();
+    let select_level = |level: crate::configs::ExperienceLevel| {
+        let active_level = active_level.clone();
+        Callback::from(move |_: MouseEvent| active_level.set(level))
+    };
+    {
+        let rookie_ref = rookie_ref.clone();
+        let expert_ref = expert_ref.clone();
+        crate::components::use_keydown(
+            *active_level,
+            Callback::from(move |event: web_sys::KeyboardEvent| {
+                if event.key() != crate::components::SWITCH_MODE_KEY
+                    || crate::components::is_typing_in_field(&event)
+                {
+                    return;
+                }
+                let other_tab = match *active_level {
+                    crate::configs::ExperienceLevel::Rookie => &expert_ref,
+                    crate::configs::ExperienceLevel::Expert => &rookie_ref,
+                };
+                if let Some(trigger) = other_tab
+                    .cast::<web_sys::HtmlElement>()
+                    .and_then(|wrapper| wrapper.first_element_child())
+                    .and_then(|trigger| trigger.dyn_into::<web_sys::HtmlElement>().ok())
+                {
+                    event.prevent_default();
+                    trigger.click();
+                }
+            }),
+        );
+    }
 
     html! {
             <TabsList class="flex flex-1">
-                <TabsTrigger value={crate::configs::ExperienceLevel::Rookie.as_ref()}>
-                    { language_ctx.t("common_rookie") }
-                </TabsTrigger>
-                <TabsTrigger value={crate::configs::ExperienceLevel::Expert.as_ref()}>
-                    { language_ctx.t("common_expert") }
-                </TabsTrigger>
+                <div ref={rookie_ref} class="flex flex-1"
+                    onclick={select_level(crate::configs::ExperienceLevel::Rookie)}>
+                    <TabsTrigger value={crate::configs::ExperienceLevel::Rookie.as_ref()}>
+                        { language_ctx.t("common_rookie") }
+                    </TabsTrigger>
+                </div>
+                <div ref={expert_ref} class="flex flex-1"
+                    onclick={select_level(crate::configs::ExperienceLevel::Expert)}>
+                    <TabsTrigger value={crate::configs::ExperienceLevel::Expert.as_ref()}>
+                        { language_ctx.t("common_expert") }
+                    </TabsTrigger>
+                </div>
             </TabsList>
 
     }
@@ -419,6 +813,7 @@ pub fn game_details_form(props: &GameDetailsFormProps) -> Html {
     let language_ctx = crate::contexts::language::use_language_ctx();
 
     let selected_event = use_state(|| game.event.clone());
+    let selected_variant = use_state(|| game_ctx.variant());
     let is_tournament = matches!(
         *selected_event,
         rooky_core::pgn_standards::PgnEvent::Named(_)
@@ -436,6 +831,8 @@ pub fn game_details_form(props: &GameDetailsFormProps) -> Html {
                     class="space-y-4"
                     onsubmit={{
                         let game_ctx = game_ctx.clone();
+                        let selected_variant = selected_variant.clone();
+                        let replace_message = language_ctx.t("game_details_variant_restart");
                         Callback::from(move |form: web_sys::HtmlFormElement| {
                             let white_input = form.get_with_name("white")
                                 .and_then(|n| n.dyn_into::<web_sys::HtmlInputElement>().ok());
@@ -450,42 +847,39 @@ pub fn game_details_form(props: &GameDetailsFormProps) -> Html {
                             let round_input = form.get_with_name("round")
                                 .and_then(|n| n.dyn_into::<web_sys::HtmlInputElement>().ok());
 
-                            if let Some(white) = white_input {
-                                let white_value = white.value();
-                                if !white_value.is_empty() {
-                                    game_ctx.dispatch(crate::live_game::AnnotatedGameAction::AddWhiteName(white_value));
-                                }
-                            }
-                            if let Some(black) = black_input {
-                                let black_value = black.value();
-                                if !black_value.is_empty() {
-                                    game_ctx.dispatch(crate::live_game::AnnotatedGameAction::AddBlackName(black_value));
-                                }
-                            }
-                            if let Some(date) = date_input {
-                                if let Ok(parsed_date) = chrono::NaiveDate::parse_from_str(&date.value(), "%Y-%m-%d") {
-                                    game_ctx.dispatch(crate::live_game::AnnotatedGameAction::ChangeDate(parsed_date));
-                                }
-                            }
-                            if let Some(event) = event_input {
+                            let white = white_input.map(|white| white.value()).filter(|white| !white.is_empty());
+                            let black = black_input.map(|black| black.value()).filter(|black| !black.is_empty());
+                            let date = date_input.and_then(|date| {
+                                chrono::NaiveDate::parse_from_str(&date.value(), "%Y-%m-%d").ok()
+                            });
+                            let event = event_input.map(|event| {
                                 let event_value = event.value();
-                                let site_value = site_input.map(|s| s.value()).unwrap_or_default();
-                                let round_value = round_input.map(|r| r.value()).unwrap_or_default();
-
                                 if event_value == "Casual" {
-                                    game_ctx.dispatch(crate::live_game::AnnotatedGameAction::UpdateEventDetails {
-                                        event: "Casual".to_string(),
-                                        site: String::new(),
-                                        round: String::new(),
-                                    });
+                                    ("Casual".to_string(), String::new(), String::new())
                                 } else {
-                                    game_ctx.dispatch(crate::live_game::AnnotatedGameAction::UpdateEventDetails {
-                                        event: event_value,
-                                        site: site_value,
-                                        round: round_value,
-                                    });
+                                    let site_value = site_input.map(|s| s.value()).unwrap_or_default();
+                                    let round_value = round_input.map(|r| r.value()).unwrap_or_default();
+                                    (event_value, site_value, round_value)
                                 }
-                            }
+                            });
+                            // Changing the variant starts the game over, so moves are only dropped after confirming
+                            let variant = if *selected_variant == game_ctx.variant() {
+                                None
+                            } else {
+                                let confirmed = game_ctx.game_tree().mainline().is_empty()
+                                    || web_sys::window()
+                                        .and_then(|window| window.confirm_with_message(&replace_message).ok())
+                                        .unwrap_or_default();
+                                confirmed.then_some(*selected_variant)
+                            };
+                            // One save is a single edit to undo
+                            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::UpdateDetails {
+                                white,
+                                black,
+                                date,
+                                event,
+                                variant,
+                            });
                         })
                     }}
                 >
@@ -584,6 +978,30 @@ pub fn game_details_form(props: &GameDetailsFormProps) -> Html {
                         </>
                     }
 
+                    <div class="space-y-2">
+                        <label class="text-sm font-medium text-foreground">{ language_ctx.t("game_details_variant") }</label>
+                        <Select::<crate::models::GameVariant>
+                            name="variant"
+                            onchange={{
+                                let selected_variant = selected_variant.setter();
+                                Callback::from(move |value: Option<crate::models::GameVariant>| {
+                                    if let Some(variant) = value {
+                                        selected_variant.set(variant);
+                                    }
+                                })
+                            }}
+                            >
+                            <SelectTrigger::<crate::models::GameVariant> class="w-full" />
+                            <SelectContent::<crate::models::GameVariant>>
+                                { for crate::models::GameVariant::ALL.iter().map(|variant| html! {
+                                    <SelectItem::<crate::models::GameVariant> value={*variant}
+                                        label={variant.pgn_name().to_string()}
+                                    />
+                                }) }
+                            </SelectContent::<crate::models::GameVariant>>
+                        </Select::<crate::models::GameVariant>>
+                    </div>
+
                     // Submit button
                     <shady_minions::ui::Button
                         r#type={shady_minions::ui::ButtonType::Submit}
@@ -600,60 +1018,137 @@ pub fn game_details_form(props: &GameDetailsFormProps) -> Html {
 #[function_component(MoveList)]
 pub fn move_list() -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
-    let moves = game_ctx.pgn_game().moves.clone();
+    let notation = crate::configs::use_annotator_config().san_notation();
+    let tree = game_ctx.game_tree();
+    let cursor = game_ctx.cursor();
 
     // Create a reference to the container for scrolling
     let container_ref = use_node_ref();
 
-    // Use effect to scroll to the end when moves update
+    // Keep the current move in view when the cursor moves
     {
         let container_ref = container_ref.clone();
-        let moves_len = moves.len();
-        use_effect_with(moves_len, move |_| {
+        use_effect_with(cursor, move |_| {
             if let Some(container) = container_ref.cast::<web_sys::HtmlElement>() {
-                let scroll_width = container.scroll_width();
-                container.set_scroll_left(scroll_width);
+                let current = container
+                    .query_selector("[data-current-move]")
+                    .ok()
+                    .flatten()
+                    .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+                match current {
+                    Some(current) => container
+                        .set_scroll_left(current.offset_left() - container.client_width() / 2),
+                    None => container.set_scroll_left(0),
+                }
             }
             || ()
         });
     }
 
+    let onselect = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |id: crate::models::MoveId| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::GoToPly(id));
+        })
+    };
+
     html! {
         <div
             ref={container_ref}
-            class="flex flex-row p-3 items-center w-full overflow-x-auto whitespace-nowrap gap-3 pb-2 max-w-sm min-h-12 bg-background rounded-lg"
+            class="flex flex-row p-3 items-center w-full overflow-x-auto whitespace-nowrap gap-2 pb-2 max-w-sm min-h-12 bg-background rounded-lg text-sm text-muted"
         >
-            { for moves.chunks(2).enumerate().map(|(index, m)| {
-                let white_move = m.first().expect("White move");
-                let black_move = m.get(1);
-                let is_last_chunk = index == moves.chunks(2).count() - 1;
-
-                let move_number = index + 1;
+            { crate::components::pre_game_comment_html(tree) }
+            { crate::components::line_html(tree, crate::models::GameTree::ROOT, true, Some(cursor), &onselect, notation) }
+        </div>
+    }
+}
 
-                let white_class = if is_last_chunk && black_move.is_none() {
-                    "text-lg font-semibold"
-                } else {
-                    "text-sm"
-                };
+#[function_component(MoveNavigation)]
+pub fn move_navigation() -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let tree = game_ctx.game_tree();
+    let cursor = game_ctx.cursor();
+    let in_variation = !tree.is_mainline(cursor);
 
-                let black_class = if is_last_chunk && black_move.is_some() {
-                    "text-lg font-semibold text-muted"
-                } else {
-                    "text-sm, text-muted"
-                };
+    let dispatch = {
+        let game_ctx = game_ctx.clone();
+        move |action: fn() -> crate::live_game::AnnotatedGameAction| {
+            let game_ctx = game_ctx.clone();
+            Callback::from(move |_: MouseEvent| game_ctx.dispatch(action()))
+        }
+    };
+    let promote = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::PromoteVariation(
+                cursor,
+            ));
+        })
+    };
+    // The whole variation goes, not only the moves after the cursor
+    let delete = {
+        let game_ctx = game_ctx.clone();
+        let start = tree.variation_start(cursor).unwrap_or(cursor);
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::DeleteVariation(
+                start,
+            ));
+        })
+    };
 
-                html! {
-                    <div class="inline-flex items-center">
-                        <span class="text-secondary-foreground mr-1 text-sm">{ format!("{}.", move_number) }</span>
-                        <span class={classes!(white_class, "mr-1", "text-muted")}>{ white_move.to_string() }</span>
-                        { if let Some(black_move) = black_move {
-                            html! { <span class={black_class}>{ black_move.to_string() }</span> }
-                        } else {
-                            html! {}
-                        }}
-                    </div>
-                }
-            })}
+    html! {
+        <div class="flex flex-wrap gap-1 mt-2 w-full max-w-sm mx-auto justify-center items-center">
+            <Button
+                onclick={dispatch(|| crate::live_game::AnnotatedGameAction::GoToStart)}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={shady_minions::ui::ButtonVariant::Outline}>
+                <lucide_yew::ChevronsLeft class="size-4" />
+            </Button>
+            <Button
+                onclick={dispatch(|| crate::live_game::AnnotatedGameAction::StepBack)}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={shady_minions::ui::ButtonVariant::Outline}>
+                <lucide_yew::ChevronLeft class="size-4" />
+            </Button>
+            <Button
+                onclick={dispatch(|| crate::live_game::AnnotatedGameAction::StepForward)}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={shady_minions::ui::ButtonVariant::Outline}>
+                <lucide_yew::ChevronRight class="size-4" />
+            </Button>
+            <Button
+                onclick={dispatch(|| crate::live_game::AnnotatedGameAction::GoToEnd)}
+                size={shady_minions::ui::ButtonSize::Small}
+                variant={shady_minions::ui::ButtonVariant::Outline}>
+                <lucide_yew::ChevronsRight class="size-4" />
+            </Button>
+            <crate::components::AnnotationEditor />
+            if in_variation {
+                <>
+                <Button
+                    onclick={promote}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::ArrowUpToLine class="size-4 mr-1" />
+                    <span class="text-xs">{ language_ctx.t("variation_promote") }</span>
+                </Button>
+                <Button
+                    onclick={delete}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Destructive}>
+                    <lucide_yew::Trash2 class="size-4 mr-1" />
+                    <span class="text-xs">{ language_ctx.t("variation_delete") }</span>
+                </Button>
+                <Button
+                    onclick={dispatch(|| crate::live_game::AnnotatedGameAction::ReturnToMainLine)}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::CornerUpLeft class="size-4 mr-1" />
+                    <span class="text-xs">{ language_ctx.t("variation_main_line") }</span>
+                </Button>
+                </>
+            }
         </div>
     }
 }
@@ -662,6 +1157,7 @@ pub fn move_list() -> Html {
 pub fn relay_status_icon() -> Html {
     let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
         .expect("missing relay context");
+    let language_ctx = crate::contexts::language::use_language_ctx();
     let relay_status_state = use_state(Vec::new);
     let relay_set = relay_status_state.setter();
 
@@ -709,7 +1205,7 @@ pub fn relay_status_icon() -> Html {
             "text-orange-500",
             format!("{}/{}", open_relays, total_relays),
         ),
-        "disconnected" => ("text-red-500", "Offline".to_string()),
+        "disconnected" => ("text-red-500", language_ctx.t("relay_status_offline")),
         _ => ("text-gray-400", "...".to_string()),
     };
 
@@ -0,0 +1,324 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use crate::models::{NostrMetadata, CHAT_MESSAGE_KIND, GIFT_WRAP_KIND};
+use crate::router::AnnotatorRoute;
+use nostr_minions::nostro2::NostrNote;
+use shady_minions::ui::{Button, Card, CardContent, CardHeader, CardTitle};
+use web_sys::MouseEvent;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Game received as a direct message.
+#[derive(Clone, Debug, PartialEq)]
+struct ReceivedGame {
+    /// Decrypted message, its content is the PGN.
+    rumor: NostrNote,
+    game: crate::models::PgnGame,
+}
+
+impl ReceivedGame {
+    /// Keeps the opened message only if it holds a game.
+    fn from_rumor(rumor: NostrNote) -> Option<Self> {
+        if rumor.kind != CHAT_MESSAGE_KIND {
+            return None;
+        }
+        // Plain chat messages read as an empty game, a game has headers or moves
+        let game = crate::models::parse_pgn(&rumor.content)
+            .filter(|game| !game.headers.is_empty() || !game.tree.mainline().is_empty())?;
+        Some(Self { rumor, game })
+    }
+}
+
+/// Games received by direct message, with the profiles of their senders.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Inbox {
+    games: Vec<ReceivedGame>,
+    /// Latest kind 0 metadata of each sender, by pubkey.
+    senders: HashMap<String, (i64, NostrMetadata)>,
+}
+
+enum InboxAction {
+    Received(Vec<ReceivedGame>),
+    SenderProfile(NostrNote),
+}
+
+impl Reducible for Inbox {
+    type Action = InboxAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut inbox = (*self).clone();
+        match action {
+            InboxAction::Received(games) => {
+                for game in games {
+                    if !inbox
+                        .games
+                        .iter()
+                        .any(|known| known.rumor.id == game.rumor.id)
+                    {
+                        inbox.games.push(game);
+                    }
+                }
+                inbox
+                    .games
+                    .sort_by(|a, b| b.rumor.created_at.cmp(&a.rumor.created_at));
+            }
+            InboxAction::SenderProfile(note) => {
+                let created_at = note.created_at;
+                let pubkey = note.pubkey.clone();
+                let Ok(metadata) = NostrMetadata::try_from(note) else {
+                    return self;
+                };
+                if inbox
+                    .senders
+                    .get(&pubkey)
+                    .is_some_and(|(known, _)| *known >= created_at)
+                {
+                    return self;
+                }
+                inbox.senders.insert(pubkey, (created_at, metadata));
+            }
+        }
+        Rc::new(inbox)
+    }
+}
+
+/// Games sent to us as NIP-17 direct messages, to review or to keep annotating.
+#[function_component(InboxPage)]
+pub fn inbox_page() -> Html {
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
+        .expect("Relay context not found");
+    let signer = crate::signer::use_signer().signer();
+    let inbox = use_reducer(Inbox::default);
+    // Gift wraps already opened, relays send them again with every new note
+    let opened = use_mut_ref(HashSet::<String>::new);
+
+    // Gift wraps are addressed to us with a `p` tag, their author is a throwaway key
+    {
+        let relay_ctx = relay_ctx.clone();
+        let pubkey = signer.as_ref().map(|signer| signer.pubkey().to_string());
+        use_effect_with(pubkey, move |pubkey| {
+            if let Some(pubkey) = pubkey {
+                let mut filter = nostr_minions::nostro2::NostrSubscription {
+                    kinds: Some(vec![GIFT_WRAP_KIND]),
+                    ..Default::default()
+                };
+                filter.add_tag("#p", pubkey);
+                relay_ctx.send(filter);
+            }
+            || {}
+        });
+    }
+    {
+        let inbox = inbox.clone();
+        let opened = opened.clone();
+        use_effect_with(
+            (relay_ctx.unique_notes.clone(), signer),
+            move |(notes, signer)| {
+                if let Some(signer) = signer.clone() {
+                    let gift_wraps = notes
+                        .iter()
+                        .filter(|note| note.kind == GIFT_WRAP_KIND)
+                        .filter(|note| {
+                            note.id
+                                .clone()
+                                .is_some_and(|id| opened.borrow_mut().insert(id))
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if !gift_wraps.is_empty() {
+                        let inbox = inbox.clone();
+                        // Extensions and remote signers decrypt one message at a time
+                        yew::platform::spawn_local(async move {
+                            let mut games = vec![];
+                            for gift_wrap in &gift_wraps {
+                                if let Ok(rumor) = signer.open_gift_wrap(gift_wrap).await {
+                                    games.extend(ReceivedGame::from_rumor(rumor));
+                                }
+                            }
+                            if !games.is_empty() {
+                                inbox.dispatch(InboxAction::Received(games));
+                            }
+                        });
+                    }
+                }
+                for note in notes.iter().filter(|note| note.kind == 0) {
+                    inbox.dispatch(InboxAction::SenderProfile(note.clone()));
+                }
+                || {}
+            },
+        );
+    }
+    // Profiles of new senders are asked for once they show up
+    {
+        let relay_ctx = relay_ctx.clone();
+        let mut senders = inbox
+            .games
+            .iter()
+            .map(|game| game.rumor.pubkey.clone())
+            .collect::<Vec<_>>();
+        senders.sort();
+        senders.dedup();
+        use_effect_with(senders, move |senders| {
+            if !senders.is_empty() {
+                relay_ctx.send(nostr_minions::nostro2::NostrSubscription {
+                    kinds: Some(vec![0]),
+                    authors: Some(senders.clone()),
+                    ..Default::default()
+                });
+            }
+            || {}
+        });
+    }
+
+    html! {
+        <>
+            <yew_router::components::Link<AnnotatorRoute> to={AnnotatorRoute::Home}>
+                <Button
+                    class="fixed top-4 left-4 z-50"
+                    variant={shady_minions::ui::ButtonVariant::Outline}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    >
+                    <lucide_yew::ArrowLeft class="size-4" />
+                </Button>
+            </yew_router::components::Link<AnnotatorRoute>>
+            <Card class="max-w-sm h-fit mx-auto mt-16">
+                <CardHeader>
+                    <CardTitle>{ language_ctx.t("inbox_title") }</CardTitle>
+                </CardHeader>
+                <CardContent>
+                    {if inbox.games.is_empty() {
+                        html! {
+                            <div class="text-center py-8 text-muted-foreground">
+                                <lucide_yew::Inbox class="w-12 h-12 mx-auto mb-2 opacity-50" />
+                                <p>{ language_ctx.t("inbox_empty") }</p>
+                            </div>
+                        }
+                    } else {
+                        html! {
+                            <div class="space-y-3">
+                                { for inbox.games.iter().map(|game| html! {
+                                    <ReceivedGameCard
+                                        key={game.rumor.id.clone().unwrap_or_default()}
+                                        game={game.clone()}
+                                        sender={inbox.senders.get(&game.rumor.pubkey).map(|(_, metadata)| metadata.clone())} />
+                                }) }
+                            </div>
+                        }
+                    }}
+                </CardContent>
+            </Card>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq, Clone)]
+struct ReceivedGameCardProps {
+    game: ReceivedGame,
+    sender: Option<NostrMetadata>,
+}
+
+#[function_component(ReceivedGameCard)]
+fn received_game_card(props: &ReceivedGameCardProps) -> Html {
+    let game_ctx = crate::live_game::use_annotated_game();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let navigator = use_navigator().expect("Navigator not found");
+    let game = &props.game.game;
+    let header = |name: &str| {
+        game.header(name)
+            .filter(|value| *value != "?")
+            .unwrap_or_default()
+            .to_string()
+    };
+    let sender = props.sender.clone().unwrap_or_default();
+    let picture = sender
+        .picture
+        .clone()
+        .unwrap_or_else(|| "/public/assets/img/default-avatar.png".to_string());
+
+    // The game goes along with the route, a received game isn't saved to the library
+    let open_review = {
+        let navigator = navigator.clone();
+        let state = (
+            props.game.rumor.id.clone().unwrap_or_default(),
+            crate::live_game::LiveGameEntry::from_pgn_game(game),
+        );
+        Callback::from(move |_: MouseEvent| {
+            navigator.push_with_state(&AnnotatorRoute::ReceivedGame, state.clone());
+        })
+    };
+    let load_for_editing = {
+        let entry = crate::live_game::LiveGameEntry::from_pgn_game(game);
+        let message = language_ctx.t("games_replace_current");
+        Callback::from(move |_: MouseEvent| {
+            if crate::components::replace_live_game(&game_ctx, entry.clone(), &message) {
+                navigator.push(&AnnotatorRoute::Home);
+            }
+        })
+    };
+
+    html! {
+        <div class="flex items-center justify-between gap-2 p-3 border border-muted rounded-md">
+            <img src={picture} alt={sender.name.clone()} class="size-8 rounded-full object-cover shrink-0" />
+            <div class="flex flex-col flex-1 overflow-hidden">
+                <span class="font-medium truncate">
+                    { format!("{} - {}", header("White"), header("Black")) }
+                </span>
+                <span class="text-xs text-muted-foreground truncate">
+                    { [sender.name, header("Event"), header("Result")]
+                        .into_iter()
+                        .filter(|value| !value.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" · ") }
+                </span>
+            </div>
+            <div class="flex gap-1">
+                <Button
+                    onclick={open_review}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::Eye class="size-4" />
+                </Button>
+                <Button
+                    onclick={load_for_editing}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                    <lucide_yew::Pencil class="size-4" />
+                </Button>
+            </div>
+        </div>
+    }
+}
+
+/// Review page for a game opened from the inbox, shown without saving it or
+/// replacing the live game.
+#[function_component(ReceivedGameReviewPage)]
+pub fn received_game_review_page() -> Html {
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    // Message id and game, only there when coming from the inbox, a reloaded page has lost them
+    let received = use_location()
+        .and_then(|location| location.state::<(String, crate::live_game::LiveGameEntry)>());
+
+    match received.as_deref() {
+        Some((id, entry)) => html! {
+            <crate::live_game::StoredGameProvider key={id.clone()} entry={entry.clone()}>
+                <crate::ReviewPage />
+            </crate::live_game::StoredGameProvider>
+        },
+        None => html! {
+            <Card class="max-w-sm h-fit mx-auto mt-16">
+                <CardContent class="text-center py-8 space-y-4 text-muted-foreground">
+                    <lucide_yew::FileX class="w-12 h-12 mx-auto opacity-50" />
+                    <p>{ language_ctx.t("games_not_found") }</p>
+                    <yew_router::components::Link<AnnotatorRoute> to={AnnotatorRoute::Inbox}>
+                        <Button variant={shady_minions::ui::ButtonVariant::Outline}>
+                            <lucide_yew::ArrowLeft class="size-4 mr-2" />
+                            { language_ctx.t("inbox_back") }
+                        </Button>
+                    </yew_router::components::Link<AnnotatorRoute>>
+                </CardContent>
+            </Card>
+        },
+    }
+}
@@ -65,25 +65,24 @@ fn key_recovery_section() -> Html {
     let recovery_phrase = use_state(Vec::<String>::new);
     let id_state = use_state(|| key_ctx.get_identity().cloned());
 
-    // Check if user is using extension-based identity
-    let is_extension = use_state(|| false);
+    // Extensions and remote signers keep the key to themselves
+    let signer_ctx = crate::signer::use_signer();
+    let is_extension = signer_ctx.signer().is_some_and(|signer| !signer.is_local());
 
     // Fetch key information and determine if user is using extension
     let priv_key_handle = priv_key.clone();
     let recovery_phrase_handle = recovery_phrase.clone();
-    // let is_extension_handle = is_extension.clone();
     let id_handle = id_state.clone();
     let pubkey_setter = pubkey.setter();
+    let signer_pubkey = signer_ctx.pubkey();
     let notification_text = language_ctx.t("notification_copied_to_clipboard");
     use_effect_with(key_ctx.clone(), move |key_handle| {
         let priv_key_handle = priv_key_handle.clone();
         let recovery_phrase_handle = recovery_phrase_handle.clone();
-        // let is_extension_handle = is_extension_handle.clone();
         let key_handle = key_handle.clone();
 
         let pubkey_setter = pubkey_setter.clone();
         yew::platform::spawn_local(async move {
-            // Check if identity is extension-based by attempting to get the key
             id_handle.set(key_handle.get_identity().cloned());
 
             if let Some(mut key) = key_handle.get_nostr_key().await {
@@ -110,6 +109,8 @@ fn key_recovery_section() -> Html {
                         mnemonic.split_whitespace().map(String::from).collect();
                     recovery_phrase_handle.set(words);
                 }
+            } else if let Some(pubkey) = signer_pubkey {
+                pubkey_setter.set(pubkey);
             }
         });
         || {}
@@ -159,6 +160,7 @@ fn key_recovery_section() -> Html {
 
     let delete_key = {
         let key_handle = key_ctx.dispatcher();
+        let signer_handle = signer_ctx.clone();
         let id_state = id_state.clone();
         let lang_ctx = language_ctx.clone();
         Callback::from(move |_| {
@@ -169,8 +171,11 @@ fn key_recovery_section() -> Html {
             }) {
                 // First dispatch the DeleteIdentity action
                 key_handle.dispatch(NostrIdAction::DeleteIdentity);
+                signer_handle.dispatch(crate::signer::SignerAction::SignOut);
                 let id = (*id_state).clone();
                 yew::platform::spawn_local(async move {
+                    // Extension and remote signer logins only leave how to reconnect
+                    crate::models::LoginMethodEntry::forget().await;
                     let Some(id) = id else {
                         web_sys::console::log_1(&"No identity found to delete".into());
                         return;
@@ -191,7 +196,7 @@ fn key_recovery_section() -> Html {
             <div class="flex gap-3 w-full max-w-xs">
                 // Show/Hide Toggle Button
                 {
-                    if !(*is_extension) {
+                    if !is_extension {
                         html! {
                             <Button
                                 onclick={
@@ -272,17 +277,16 @@ fn key_recovery_section() -> Html {
                     <h3 class="text-lg font-medium text-muted">{ language_ctx.t("key_recovery_private_key") }</h3>
 
                     {
-                        // if *is_extension {
-                        //     html! {
-                        //         <div class="bg-muted p-4 rounded-lg">
-                        //             <div class="flex items-center text-gray-700 space-x-2">
-                        //                 <lucide_yew::TriangleAlert class="text-amber-500 w-5 h-5 flex-shrink-0" />
-                        //                 // <p>{ language_ctx.t("key_recovery_no_private_key") }</p>
-                        //             </div>
-                        //         </div>
-                        //     }
-                        // } else
-                        if *show_sensitive {
+                        if is_extension {
+                            html! {
+                                <div class="bg-muted p-4 rounded-lg">
+                                    <div class="flex items-center text-muted-foreground space-x-2">
+                                        <lucide_yew::TriangleAlert class="text-amber-500 w-5 h-5 flex-shrink-0" />
+                                        <p>{ language_ctx.t("key_recovery_no_private_key") }</p>
+                                    </div>
+                                </div>
+                            }
+                        } else if *show_sensitive {
                             html! {
                                 <div class="bg-muted p-4 rounded-lg overflow-x-auto flex gap-3">
                                     // <pre class="text-sm text-gray-800 whitespace-pre-wrap break-all select-all">
@@ -326,17 +330,16 @@ fn key_recovery_section() -> Html {
                 <div class="space-y-2">
                     <h3 class="text-lg font-medium text-muted">{ language_ctx.t("key_recovery_recovery_phrase") }</h3>
                     {
-                        // if *is_extension {
-                        //     html! {
-                        //         <div class="bg-muted p-4 rounded-lg">
-                        //             <div class="flex items-center text-gray-700 space-x-2">
-                        //                 <lucide_yew::TriangleAlert class="text-amber-500 w-5 h-5 flex-shrink-0" />
-                        //     //            <p>{ language_ctx.t("key_recovery_extension_warning") }</p>
-                        //             </div>
-                        //         </div>
-                        //     }
-                        // } else
-                        if *show_sensitive {
+                        if is_extension {
+                            html! {
+                                <div class="bg-muted p-4 rounded-lg">
+                                    <div class="flex items-center text-muted-foreground space-x-2">
+                                        <lucide_yew::TriangleAlert class="text-amber-500 w-5 h-5 flex-shrink-0" />
+                                        <p>{ language_ctx.t("key_recovery_extension_warning") }</p>
+                                    </div>
+                                </div>
+                            }
+                        } else if *show_sensitive {
                             if recovery_phrase.is_empty() {
                                 html! {
                                     <div class="bg-muted p-4 rounded-lg">
@@ -10,7 +10,9 @@ use yew::prelude::*;
 pub fn login_form() -> Html {
     let open_modal = use_state(|| false);
     let login_modal = use_state(|| false);
+    let signer_modal = use_state(|| false);
     let language_ctx = crate::contexts::language::use_language_ctx();
+    let signer_ctx = crate::signer::use_signer();
 
     let onclick = {
         let modal = open_modal.clone();
@@ -24,6 +26,42 @@ pub fn login_form() -> Html {
             modal.set(!(*modal));
         })
     };
+    let signer_onclick = {
+        let modal = signer_modal.clone();
+        Callback::from(move |_| {
+            modal.set(!(*modal));
+        })
+    };
+    let extension_onclick = {
+        let failed_message = language_ctx.t("login_signer_failed");
+        Callback::from(move |_| {
+            let signer_ctx = signer_ctx.clone();
+            let failed_message = failed_message.clone();
+            yew::platform::spawn_local(async move {
+                let pubkey = match crate::models::extension_pubkey().await {
+                    Ok(pubkey) => pubkey,
+                    Err(e) => {
+                        web_sys::console::error_1(&format!("Extension login failed: {e}").into());
+                        nostr_minions::widgets::toastify::ToastifyOptions::new_failure(
+                            &failed_message,
+                        )
+                        .show();
+                        return;
+                    }
+                };
+                let entry =
+                    crate::models::LoginMethodEntry::new(crate::models::LoginMethod::Extension {
+                        pubkey: pubkey.clone(),
+                    });
+                if let Err(e) = entry.save_to_store().await {
+                    web_sys::console::error_1(&e);
+                }
+                signer_ctx.dispatch(crate::signer::SignerAction::SignIn(
+                    crate::models::Signer::Extension { pubkey },
+                ));
+            });
+        })
+    };
     html! {
         <div class="flex flex-col items-center justify-center min-h-screen px-6">
         <img
@@ -40,6 +78,7 @@ pub fn login_form() -> Html {
                     <TabsList class={classes!("justify-stretch", "w-full", "flex")}>
                         <TabsTrigger value="register">{ language_ctx.t("login_new_identity") }</TabsTrigger>
                         <TabsTrigger value="login">{ language_ctx.t("login_recover") }</TabsTrigger>
+                        <TabsTrigger value="signer">{ language_ctx.t("login_signer") }</TabsTrigger>
                     </TabsList>
                     <TabsContent value="register">
                         <p class={classes!("text-sm", "text-muted-foreground")}>
@@ -63,6 +102,27 @@ pub fn login_form() -> Html {
                             { language_ctx.t("login_input_key") }
                         </Button>
                     </TabsContent>
+                    <TabsContent value="signer">
+                        <p class={classes!("text-sm", "text-muted-foreground")}>
+                            { language_ctx.t("login_signer_message") }
+                        </p>
+                        <div class={classes!("flex", "flex-wrap", "gap-2", "mt-4")}>
+                            <Button
+                                onclick={extension_onclick}
+                                disabled={!crate::models::has_extension()}
+                                r#type={shady_minions::ui::ButtonType::Button}
+                                class={classes!("flex-1")}>
+                                { language_ctx.t("login_extension") }
+                            </Button>
+                            <Button
+                                onclick={signer_onclick}
+                                variant={shady_minions::ui::ButtonVariant::Outline}
+                                r#type={shady_minions::ui::ButtonType::Button}
+                                class={classes!("flex-1")}>
+                                { language_ctx.t("login_remote_signer") }
+                            </Button>
+                        </div>
+                    </TabsContent>
                 </Tabs>
             </CardContent>
         </Card>
@@ -72,6 +132,9 @@ pub fn login_form() -> Html {
         <Modal is_open={login_modal} >
             <LoginForm />
         </Modal>
+        <Modal is_open={signer_modal} >
+            <RemoteSignerForm />
+        </Modal>
         </div>
     }
 }
@@ -274,7 +337,7 @@ pub fn new_key_form() -> Html {
             <CardHeader>
                 <CardTitle>{ language_ctx.t("login_new_key") }</CardTitle>
                 <CardDescription class={classes!("flex-1")}>
-                    { language_ctx.t(r#"login_key_secret_message"#) }
+                    { language_ctx.t("login_key_secret_message") }
                 </CardDescription>
             </CardHeader>
             <CardContent class={classes!("space-y-4")}>
@@ -334,3 +397,168 @@ pub fn new_key_form() -> Html {
         </Card>
     }
 }
+
+/// Relay a signer is asked to answer a `nostrconnect://` link on.
+const NOSTR_CONNECT_RELAY: &str = "wss://relay.nsec.app";
+
+/// Login with a NIP-46 remote signer, from its `bunker://` link or by
+/// showing it a `nostrconnect://` one. The key never leaves the signer.
+#[function_component(RemoteSignerForm)]
+pub fn remote_signer_form() -> Html {
+    let signer_ctx = crate::signer::use_signer();
+    let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
+        .expect("Relay context not found");
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let connecting = use_state(|| false);
+    let failed = use_state(|| false);
+    let connect_uri = use_state(|| None::<String>);
+
+    // Both links log in the same way once the signer accepts
+    let sign_in = {
+        let connecting = connecting.clone();
+        let failed = failed.clone();
+        move |session: crate::models::RemoteSigner| {
+            signer_ctx.dispatch(crate::signer::SignerAction::Connect(session.clone()));
+            connecting.set(true);
+            failed.set(false);
+            let signer_ctx = signer_ctx.clone();
+            let connecting = connecting.clone();
+            let failed = failed.clone();
+            yew::platform::spawn_local(async move {
+                let pubkey = match session.connect().await {
+                    Ok(pubkey) => pubkey,
+                    Err(e) => {
+                        web_sys::console::error_1(&format!("Remote signer failed: {e}").into());
+                        connecting.set(false);
+                        failed.set(true);
+                        return;
+                    }
+                };
+                if let Some(login) = session.login(&pubkey) {
+                    let entry = crate::models::LoginMethodEntry::new(
+                        crate::models::LoginMethod::Remote(login),
+                    );
+                    if let Err(e) = entry.save_to_store().await {
+                        web_sys::console::error_1(&e);
+                    }
+                }
+                signer_ctx.dispatch(crate::signer::SignerAction::SignIn(
+                    crate::models::Signer::Remote { session, pubkey },
+                ));
+            });
+        }
+    };
+    let bunker_submit = {
+        let sign_in = sign_in.clone();
+        let relay_ctx = relay_ctx.clone();
+        let failed = failed.clone();
+        Callback::from(move |form: web_sys::HtmlFormElement| {
+            let Some(input) = form
+                .get_with_name("bunker-uri")
+                .map(|input| input.unchecked_into::<web_sys::HtmlInputElement>().value())
+            else {
+                web_sys::console::log_1(&"Error: Input not found".into());
+                return;
+            };
+            let Some(session) = input
+                .parse::<crate::models::SignerUri>()
+                .ok()
+                .and_then(|uri| {
+                    crate::models::RemoteSigner::bunker(
+                        &uri,
+                        crate::signer::relay_outbox(&relay_ctx),
+                    )
+                })
+            else {
+                failed.set(true);
+                return;
+            };
+            sign_in(session);
+        })
+    };
+    let show_connect_uri = {
+        let connect_uri = connect_uri.clone();
+        Callback::from(move |_| {
+            let (session, uri) = crate::models::RemoteSigner::nostr_connect(
+                vec![NOSTR_CONNECT_RELAY.to_string()],
+                crate::signer::relay_outbox(&relay_ctx),
+            );
+            connect_uri.set(Some(uri.to_string()));
+            sign_in(session);
+        })
+    };
+
+    html! {
+        <Card>
+            <CardHeader>
+                <CardTitle>{ language_ctx.t("login_remote_signer") }</CardTitle>
+                <CardDescription class={classes!("flex-1")}>
+                    { language_ctx.t("login_signer_message") }
+                </CardDescription>
+            </CardHeader>
+            <CardContent class={classes!("space-y-4")}>
+                <Form onsubmit={bunker_submit}>
+                    <p class={classes!("font-bold", "text-muted-foreground", "select-none", "pointer-events-none")}>
+                        { language_ctx.t("login_bunker_prefix") }
+                    </p>
+                    <Input
+                        id="bunker-uri"
+                        placeholder="bunker://..."
+                        required={true}
+                        r#type={shady_minions::ui::InputType::Text}
+                        class={classes!("text-sm", "font-bold", "text-center")}/>
+                    <Button
+                        r#type={shady_minions::ui::ButtonType::Submit}
+                        disabled={*connecting}
+                        class={classes!("mt-4", "mr-4")}>
+                        { language_ctx.t("login_connect") }
+                    </Button>
+                </Form>
+                <p class={classes!("font-bold", "text-muted-foreground", "select-none", "pointer-events-none")}>
+                    { language_ctx.t("login_connect_uri") }
+                </p>
+                {match &*connect_uri {
+                    Some(uri) => {
+                        let copy_uri = {
+                            let uri = uri.clone();
+                            Callback::from(move |_| {
+                                nostr_minions::browser_api::clipboard_copy(&uri);
+                            })
+                        };
+                        html! {
+                            <div class={classes!("flex", "gap-2" , "items-center")}>
+                                <p class={classes!("flex-1", "text-sm", "max-w-xs", "break-all", "select-all")}>
+                                    { uri.clone() }
+                                </p>
+                                <Button
+                                    onclick={copy_uri}
+                                    r#type={shady_minions::ui::ButtonType::Button}
+                                    variant={shady_minions::ui::ButtonVariant::Outline}>
+                                    <lucide_yew::Copy class={classes!("h-6", "w-6", "text-muted-foreground")} />
+                                </Button>
+                            </div>
+                        }
+                    }
+                    None => html! {
+                        <Button
+                            onclick={show_connect_uri}
+                            disabled={*connecting}
+                            r#type={shady_minions::ui::ButtonType::Button}
+                            variant={shady_minions::ui::ButtonVariant::Outline}>
+                            { language_ctx.t("login_show_connect_uri") }
+                        </Button>
+                    },
+                }}
+                if *connecting {
+                    <p class={classes!("text-sm", "text-muted-foreground")}>
+                        { language_ctx.t("login_waiting_signer") }
+                    </p>
+                } else if *failed {
+                    <p class={classes!("text-sm", "text-destructive")}>
+                        { language_ctx.t("login_signer_failed") }
+                    </p>
+                }
+            </CardContent>
+        </Card>
+    }
+}
@@ -1,13 +1,19 @@
+mod games;
 mod home;
+mod inbox;
 mod key_recovery;
 mod login;
 mod profile;
 mod relay_management;
 mod review;
+mod spectator;
 
+pub use games::*;
 pub use home::*;
+pub use inbox::*;
 pub use key_recovery::*;
 pub use login::*;
 pub use profile::*;
 pub use relay_management::*;
 pub use review::*;
+pub use spectator::*;
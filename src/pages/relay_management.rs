@@ -1,5 +1,7 @@
 use nostr_minions::browser_api::IdbStoreManager;
-use shady_minions::ui::{Button, ButtonVariant, Card, CardContent, CardHeader, CardTitle, Input};
+use shady_minions::ui::{
+    Button, ButtonVariant, Card, CardContent, CardDescription, CardHeader, CardTitle, Input,
+};
 use web_sys::MouseEvent;
 use yew::prelude::*;
 
@@ -7,14 +9,20 @@ use yew::prelude::*;
 pub fn relay_management_page() -> Html {
     let new_relay_url = use_state(String::new);
     let relay_ctx = nostr_minions::relay_pool::use_nostr_relay_pool();
+    let language_ctx = crate::contexts::language::use_language_ctx();
 
     // Loading relays from IndexedDB on component mount
     let relays = relay_ctx.relay_health();
+    let connected_relays = relays
+        .values()
+        .filter(|state| **state == nostr_minions::relay_pool::ReadyState::OPEN)
+        .count();
 
     let add_relay = {
         let relays = relays.clone();
         let new_relay_url = new_relay_url.clone();
         let relay_ctx = relay_ctx.clone();
+        let language_ctx = language_ctx.clone();
 
         Callback::from(move |_: MouseEvent| {
             let mut url = (*new_relay_url).clone();
@@ -30,7 +38,7 @@ pub fn relay_management_page() -> Html {
             // Check if relay already exists
             if relays.contains_key(url.trim()) {
                 nostr_minions::widgets::toastify::ToastifyOptions::new_failure(
-                    "Relay already exists",
+                    &language_ctx.t("relay_already_exists"),
                 )
                 .show();
                 return;
@@ -45,7 +53,7 @@ pub fn relay_management_page() -> Html {
                 new_relay.clone(),
             ));
             nostr_minions::widgets::toastify::ToastifyOptions::new_success(
-                "Relay added successfully",
+                &language_ctx.t("relay_added"),
             )
             .show();
         })
@@ -53,6 +61,7 @@ pub fn relay_management_page() -> Html {
 
     let remove_relay = {
         let relay_ctx = relay_ctx.clone();
+        let language_ctx = language_ctx.clone();
 
         Callback::from(move |url: String| {
             let relay_to_delete = nostr_minions::relay_pool::UserRelay {
@@ -65,12 +74,13 @@ pub fn relay_management_page() -> Html {
                     relay_to_delete.clone(),
                 ),
             );
+            let removed_message = language_ctx.t("relay_removed");
             yew::platform::spawn_local(async move {
                 if relay_to_delete.delete_from_store().await.is_err() {
                     web_sys::console::log_1(&format!("Failed to delete relay: {}", url).into());
                 } else {
                     nostr_minions::widgets::toastify::ToastifyOptions::new_success(
-                        "Relay removed successfully",
+                        &removed_message,
                     )
                     .show();
                 }
@@ -99,7 +109,7 @@ pub fn relay_management_page() -> Html {
             </yew_router::components::Link<crate::router::AnnotatorRoute>>
             <Card class="max-w-sm h-fit mx-auto mt-16">
                 <CardHeader class="flex items-center gap-4">
-                    <CardTitle>{"Add New Relay"}</CardTitle>
+                    <CardTitle>{ language_ctx.t("relay_add_title") }</CardTitle>
                 </CardHeader>
                 <CardContent>
                     <div class="flex gap-2">
@@ -119,15 +129,18 @@ pub fn relay_management_page() -> Html {
                     </div>
                 </CardContent>
                 <CardHeader>
-                    <CardTitle>{"Connected Relays"}</CardTitle>
+                    <CardTitle>{ language_ctx.t("relay_connected_title") }</CardTitle>
+                    <CardDescription>
+                        { language_ctx.tn("relay_connected_count", connected_relays as u64) }
+                    </CardDescription>
                 </CardHeader>
                 <CardContent>
                     {if relays.is_empty() {
                         html! {
                             <div class="text-center py-8 text-muted-foreground">
                                 <lucide_yew::Wifi class="w-12 h-12 mx-auto mb-2 opacity-50" />
-                                <p>{"No relays configured"}</p>
-                                <p class="text-sm">{"Add a relay above to get started"}</p>
+                                <p>{ language_ctx.t("relay_empty") }</p>
+                                <p class="text-sm">{ language_ctx.t("relay_empty_hint") }</p>
                             </div>
                         }
                     } else {
@@ -167,15 +180,28 @@ pub struct RelayItemProps {
 
 #[function_component(RelayItem)]
 pub fn relay_item(props: &RelayItemProps) -> Html {
+    let language_ctx = crate::contexts::language::use_language_ctx();
     let (status_color, status_text, status_icon) = match props.relay {
-        nostr_minions::relay_pool::ReadyState::CONNECTING => {
-            ("text-yellow-500", "Connecting", "⏳")
-        }
-        nostr_minions::relay_pool::ReadyState::OPEN => ("text-green-500", "Connected", "✅"),
-        nostr_minions::relay_pool::ReadyState::CLOSING => {
-            ("text-orange-500", "Disconnecting", "⏳")
-        }
-        nostr_minions::relay_pool::ReadyState::CLOSED => ("text-red-500", "Disconnected", "❌"),
+        nostr_minions::relay_pool::ReadyState::CONNECTING => (
+            "text-yellow-500",
+            language_ctx.t("relay_status_connecting"),
+            "⏳",
+        ),
+        nostr_minions::relay_pool::ReadyState::OPEN => (
+            "text-green-500",
+            language_ctx.t("relay_status_connected"),
+            "✅",
+        ),
+        nostr_minions::relay_pool::ReadyState::CLOSING => (
+            "text-orange-500",
+            language_ctx.t("relay_status_disconnecting"),
+            "⏳",
+        ),
+        nostr_minions::relay_pool::ReadyState::CLOSED => (
+            "text-red-500",
+            language_ctx.t("relay_status_disconnected"),
+            "❌",
+        ),
     };
 
     html! {
@@ -1,10 +1,51 @@
 use crate::components::{DirectMessageRookyGame, SaveTxtRookyGame, ShareRookyGame};
+use crate::models::{GameTree, MoveId};
 use yew::prelude::*;
 
+/// Groups the main line into `(move number, white move, black move)` rows.
+fn mainline_rows(tree: &GameTree) -> Vec<(u32, Option<MoveId>, Option<MoveId>)> {
+    let mut rows: Vec<(u32, Option<MoveId>, Option<MoveId>)> = vec![];
+    for id in tree.mainline() {
+        match tree.move_number(id) {
+            (number, shakmaty::Color::White) => rows.push((number, Some(id), None)),
+            (number, shakmaty::Color::Black) => match rows.last_mut() {
+                Some((row_number, _, black)) if *row_number == number && black.is_none() => {
+                    *black = Some(id);
+                }
+                _ => rows.push((number, None, Some(id))),
+            },
+        }
+    }
+    rows
+}
+
 #[function_component(ReviewPage)]
 pub fn review_page() -> Html {
     let game_ctx = crate::live_game::use_annotated_game();
-    let move_list = &game_ctx.pgn_game().moves;
+    let notation = crate::configs::use_annotator_config().san_notation();
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let tree = game_ctx.game_tree();
+    let pgn = game_ctx.to_pgn();
+    let cursor = game_ctx.cursor();
+    let onselect = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |id: MoveId| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::GoToPly(id));
+        })
+    };
+    // Stored games are reviewed read-only, only the live game goes back to editing
+    let is_live_game = yew_router::hooks::use_route::<crate::router::AnnotatorRoute>()
+        == Some(crate::router::AnnotatorRoute::Review);
+    let navigator = yew_router::hooks::use_navigator();
+    let clear_outcome = {
+        let game_ctx = game_ctx.clone();
+        Callback::from(move |_: MouseEvent| {
+            game_ctx.dispatch(crate::live_game::AnnotatedGameAction::ClearOutcome);
+            if let Some(navigator) = &navigator {
+                navigator.push(&crate::router::AnnotatorRoute::Home);
+            }
+        })
+    };
 
     html! {
         <>
@@ -22,29 +63,98 @@ pub fn review_page() -> Html {
             // Header
             <div>
             <div class="text-center mb-6">
-                <h2 class="text-muted text-sm mb-2">{"Game Review"}</h2>
+                <h2 class="text-muted text-sm mb-2">{ language_ctx.t("review_title") }</h2>
                 <h1 class="text-xl font-semibold">
-                    {match game_ctx.pgn_game().outcome {
-                        shakmaty::Outcome::Draw => "Draw",
-                        shakmaty::Outcome::Decisive { winner: shakmaty::Color::White } => "White Wins",
-                        shakmaty::Outcome::Decisive { winner: shakmaty::Color::Black } => "Black Wins",
-                    }}
+                    { crate::contexts::formatting::outcome_text(&language_ctx, game_ctx.pgn_game().outcome) }
                 </h1>
+                if let Some(reason) = game_ctx.termination() {
+                    <p class="text-sm text-muted mt-1">
+                        { crate::contexts::formatting::termination_text(&language_ctx, reason) }
+                    </p>
+                }
+                if is_live_game && game_ctx.has_outcome() {
+                    <shady_minions::ui::Button
+                        onclick={clear_outcome}
+                        variant={shady_minions::ui::ButtonVariant::Outline}
+                        size={shady_minions::ui::ButtonSize::Small}
+                        class="mt-2">
+                        { language_ctx.t("outcome_clear") }
+                    </shady_minions::ui::Button>
+                }
             </div>
 
+            <crate::components::ReplayBoard />
+            <crate::components::EngineAnalysis />
+
             // Moves List
             <div class="space-y-3 mb-8 max-h-64 overflow-y-auto">
+                <p class="text-xs px-4">{ crate::components::pre_game_comment_html(tree) }</p>
                 {
-                    move_list.chunks(2).enumerate().map(|(index, chess_move_chunk)| {
-                        let move_number = index + 1;
-                        let white_move = chess_move_chunk.first().map(|m| m.to_string()).unwrap_or_default();
-                        let black_move = chess_move_chunk.get(1).map(|m| m.to_string()).unwrap_or_default();
+                    mainline_rows(tree).into_iter().map(|(move_number, white_move, black_move)| {
+                        let move_number = format!("{move_number}.");
+                        let san = |id: Option<MoveId>| {
+                            id.and_then(|id| tree.get(id)).map_or_else(
+                                || "...".to_string(),
+                                |node| {
+                                    let glyphs = node.nags.iter().map(|nag| crate::models::nag_glyph(*nag));
+                                    node.san.iter().map(|san| notation.display(san)).chain(glyphs).collect::<String>()
+                                },
+                            )
+                        };
+                        // Clicking a move shows its position on the board
+                        let move_button = |id: Option<MoveId>, class: &'static str| {
+                            let Some(id) = id else {
+                                return html! { <span class={class}>{ san(None) }</span> };
+                            };
+                            let onclick = onselect.reform(move |_: MouseEvent| id);
+                            let class = classes!(
+                                class,
+                                "cursor-pointer",
+                                "rounded",
+                                "px-1",
+                                (id == cursor).then_some("bg-secondary"),
+                            );
+                            html! { <span {class} {onclick}>{ san(Some(id)) }</span> }
+                        };
+                        // Time left after the move, when the game was timed
+                        let clock = |id: Option<MoveId>| {
+                            id.and_then(|id| tree.get(id))
+                                .and_then(|node| node.clock)
+                                .map(crate::models::format_clock)
+                                .unwrap_or_default()
+                        };
+                        let comments = [white_move, black_move]
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|id| tree.get(id).and_then(|node| node.comment.clone()))
+                            .collect::<Vec<_>>();
+                        // Alternatives to a main line move are the other children of its parent
+                        let variations = [white_move, black_move]
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|id| tree.parent(id))
+                            .flat_map(|parent| tree.children(parent).iter().skip(1).copied())
+                            .collect::<Vec<_>>();
                         html! {
+                            <>
                             <div class="flex justify-center items-center p-2 bg-background border-muted rounded-md">
                                 <span class="text-sm font-semibold mr-1">{move_number}</span>
-                                <span class="text-sm text-white">{white_move}</span>
-                                <span class="text-sm text-gray-300">{black_move}</span>
+                                { move_button(white_move, "text-sm text-white") }
+                                <span class="text-xs font-mono text-muted-foreground mx-1">{clock(white_move)}</span>
+                                if black_move.is_some() {
+                                    { move_button(black_move, "text-sm text-gray-300") }
+                                }
+                                <span class="text-xs font-mono text-muted-foreground ml-1">{clock(black_move)}</span>
                             </div>
+                            { for comments.into_iter().map(|comment| html! {
+                                <p class="text-xs italic text-muted-foreground px-4">{ comment }</p>
+                            }) }
+                            { for variations.into_iter().map(|variation| html! {
+                                <div class="flex flex-wrap items-center gap-1 px-4">
+                                    { crate::components::variation_html(tree, variation, Some(cursor), &onselect, notation) }
+                                </div>
+                            }) }
+                            </>
                         }
 
                     }).collect::<Html>()
@@ -57,14 +167,14 @@ pub fn review_page() -> Html {
               // Info Text
                 <div class="text-center mb-6 px-4">
                     <p class="text-muted text-sm leading-relaxed">
-                        {"Share your game, send it to a friend, or save it as a text file to save this game."}
+                        { language_ctx.t("review_share_description") }
                     </p>
                 </div>
 
                 <div class="flex gap-3 flex-col">
-                    <ShareRookyGame game={game_ctx.pgn_game().clone()} />
-                    <DirectMessageRookyGame game={game_ctx.pgn_game().clone()} />
-                    <SaveTxtRookyGame game={game_ctx.pgn_game().clone()} />
+                    <ShareRookyGame game={game_ctx.pgn_game().clone()} pgn={pgn.clone()} />
+                    <DirectMessageRookyGame game={game_ctx.pgn_game().clone()} pgn={pgn.clone()} />
+                    <SaveTxtRookyGame game={game_ctx.pgn_game().clone()} {pgn} />
                 </div>
             </div>
         </div>
@@ -0,0 +1,154 @@
+use crate::models::{tag_values, BroadcastContent, Naddr};
+use crate::router::AnnotatorRoute;
+use shady_minions::ui::{Button, Card, CardContent, CardHeader, CardTitle};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct SpectatorPageProps {
+    /// Address of the broadcast note, as shared by the broadcaster.
+    pub naddr: String,
+}
+
+/// Follows a live broadcast, showing the board as the game goes on.
+#[function_component(SpectatorPage)]
+pub fn spectator_page(props: &SpectatorPageProps) -> Html {
+    let language_ctx = crate::contexts::language::use_language_ctx();
+    let relay_ctx = use_context::<nostr_minions::relay_pool::NostrRelayPoolStore>()
+        .expect("Relay context not found");
+    let notation = crate::configs::use_annotator_config().san_notation();
+    let naddr = props.naddr.parse::<Naddr>().ok();
+    // The broadcaster's relays, hinted in the link, may not be in the pool yet
+    crate::components::use_session_relays(
+        naddr
+            .as_ref()
+            .map(|naddr| naddr.relays.clone())
+            .unwrap_or_default(),
+        false,
+    );
+
+    {
+        let relay_ctx = relay_ctx.clone();
+        use_effect_with(naddr.clone(), move |naddr| {
+            if let Some(naddr) = naddr {
+                let mut filter = nostr_minions::nostro2::NostrSubscription {
+                    kinds: Some(vec![naddr.kind]),
+                    authors: Some(vec![naddr.pubkey.clone()]),
+                    ..Default::default()
+                };
+                filter.add_tag("#d", &naddr.identifier);
+                relay_ctx.send(filter);
+            }
+            || {}
+        });
+    }
+
+    // Each broadcast note replaces the previous one, the latest is the game so far
+    let broadcast = naddr.as_ref().and_then(|naddr| {
+        relay_ctx
+            .unique_notes
+            .iter()
+            .filter(|note| {
+                note.kind == naddr.kind
+                    && note.pubkey == naddr.pubkey
+                    && tag_values(note, "d").contains(&naddr.identifier)
+            })
+            .max_by_key(|note| note.created_at)
+            .and_then(|note| note.content.parse::<BroadcastContent>().ok())
+    });
+    let fen = broadcast
+        .as_ref()
+        .map(|broadcast| broadcast.fen.clone())
+        .unwrap_or_default();
+    let game = broadcast
+        .as_ref()
+        .and_then(|broadcast| crate::models::parse_pgn(&broadcast.pgn));
+
+    let board_ref = use_node_ref();
+    let board_id = "spectator-board";
+    let game_board = use_mut_ref(|| None::<chessboard_js::ChessBoardJs>);
+    {
+        let board_setting = game_board.clone();
+        use_effect_with((board_ref.clone(), fen), move |(_, fen)| {
+            if !fen.is_empty() {
+                let board = crate::components::read_only_board(
+                    board_id,
+                    fen,
+                    chessboard_js::ChessboardOrientation::White,
+                );
+                *board_setting.borrow_mut() = Some(board);
+            }
+            || {}
+        });
+    }
+
+    let header = |name: &str| {
+        game.as_ref()
+            .and_then(|game| game.header(name))
+            .filter(|value| *value != "?")
+            .unwrap_or_default()
+            .to_string()
+    };
+    let result = header("Result");
+    let moves = game
+        .as_ref()
+        .map(|game| {
+            let tree = &game.tree;
+            tree.mainline()
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, id)| {
+                    let san = notation.display(tree.get(id)?.san.as_ref()?);
+                    let prefix = tree.move_number_prefix(id, index == 0);
+                    Some(prefix.map_or(san.clone(), |prefix| format!("{prefix} {san}")))
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+
+    html! {
+        <>
+            <yew_router::components::Link<AnnotatorRoute> to={AnnotatorRoute::Home}>
+                <Button
+                    class="fixed top-4 left-4 z-50"
+                    variant={shady_minions::ui::ButtonVariant::Outline}
+                    size={shady_minions::ui::ButtonSize::Small}
+                    >
+                    <lucide_yew::ArrowLeft class="size-4" />
+                </Button>
+            </yew_router::components::Link<AnnotatorRoute>>
+            <Card class="max-w-sm h-fit mx-auto mt-16">
+                <CardHeader>
+                    <CardTitle>
+                        { if game.is_some() {
+                            format!("{} - {}", header("White"), header("Black"))
+                        } else {
+                            language_ctx.t("spectator_title")
+                        } }
+                    </CardTitle>
+                </CardHeader>
+                <CardContent class="space-y-3">
+                    if naddr.is_none() {
+                        <p class="text-sm text-destructive">{ language_ctx.t("spectator_invalid_link") }</p>
+                    } else if game.is_none() {
+                        <p class="text-sm text-muted-foreground">{ language_ctx.t("spectator_waiting") }</p>
+                    } else {
+                        <div class="flex justify-between items-center text-sm">
+                            <span class="text-muted-foreground truncate">{ header("Event") }</span>
+                            if result.is_empty() || result == "*" {
+                                <span class="flex items-center gap-1 text-destructive font-semibold">
+                                    <lucide_yew::Radio class="size-4" />
+                                    { language_ctx.t("spectator_live") }
+                                </span>
+                            } else {
+                                <span class="font-semibold">{ result.clone() }</span>
+                            }
+                        </div>
+                    }
+                    <div ref={board_ref} id={board_id} class="w-full aspect-square" />
+                    <p class="text-sm font-mono break-words">{ moves }</p>
+                </CardContent>
+            </Card>
+        </>
+    }
+}
@@ -13,6 +13,16 @@ pub enum AnnotatorRoute {
     Profile,
     #[at("/review")]
     Review,
+    #[at("/games")]
+    Games,
+    #[at("/games/:id")]
+    StoredGame { id: String },
+    #[at("/inbox")]
+    Inbox,
+    #[at("/inbox/review")]
+    ReceivedGame,
+    #[at("/watch/:naddr")]
+    Spectate { naddr: String },
 }
 
 #[function_component(AnnotatorRouter)]
@@ -25,6 +35,11 @@ pub fn annotator_router() -> Html {
                 AnnotatorRoute::RelaySettings => html! { <crate::RelayManagementPage /> },
                 AnnotatorRoute::Profile => html! { <crate::ProfilePage /> },
                 AnnotatorRoute::Review => html! { <crate::ReviewPage /> },
+                AnnotatorRoute::Games => html! { <crate::GamesPage /> },
+                AnnotatorRoute::StoredGame { id } => html! { <crate::StoredGameReviewPage {id} /> },
+                AnnotatorRoute::Inbox => html! { <crate::InboxPage /> },
+                AnnotatorRoute::ReceivedGame => html! { <crate::ReceivedGameReviewPage /> },
+                AnnotatorRoute::Spectate { naddr } => html! { <crate::SpectatorPage {naddr} /> },
             }}}
         />
 